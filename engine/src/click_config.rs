@@ -0,0 +1,21 @@
+use enigo::Button as MouseButton;
+use std::time::Duration;
+
+/// The settings the runner re-reads at the start of every cycle, so edits made while a run
+/// is in progress apply without restarting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClickConfig {
+    pub interval: Duration,
+    pub clicks_count: u8,
+    pub button: MouseButton,
+}
+
+impl Default for ClickConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            clicks_count: 1,
+            button: MouseButton::Left,
+        }
+    }
+}
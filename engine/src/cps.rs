@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// Fastest rate reached with single clicks, higher targets are split into bursts.
+const MAX_SINGLE_CLICK_RATE: f64 = 50.0;
+const MAX_BURST_SIZE: f64 = 100.0;
+/// Slowest accepted target, one click a day.
+const MIN_TARGET_RATE: f64 = 1.0 / 86_400.0;
+
+/// Parses a clicks-per-second target such as `0.25` or `12.5`, down to one click a day.
+pub fn parse_target(input: &str) -> Option<f64> {
+    input
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|cps| cps.is_finite() && *cps >= MIN_TARGET_RATE)
+}
+
+/// Derives the interval and burst size that give `cps` clicks per second, `cps` comes from
+/// [`parse_target`].
+pub fn derive(cps: f64) -> (Duration, u8) {
+    let burst_size = (cps / MAX_SINGLE_CLICK_RATE)
+        .ceil()
+        .clamp(1.0, MAX_BURST_SIZE);
+    let interval = Duration::from_secs_f64(burst_size / cps);
+
+    (interval, burst_size as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_targets() {
+        assert_eq!(parse_target("12.5"), Some(12.5));
+        assert_eq!(parse_target(" 0.25 "), Some(0.25));
+        assert_eq!(parse_target("1e3"), Some(1000.0));
+    }
+
+    #[test]
+    fn rejects_targets_out_of_range() {
+        for input in ["", "fast", "0", "-1", "1e-20", "0.00001", "inf", "NaN"] {
+            assert_eq!(parse_target(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn derives_single_clicks_up_to_the_single_click_rate() {
+        assert_eq!(derive(0.25), (Duration::from_secs(4), 1));
+        assert_eq!(derive(10.0), (Duration::from_millis(100), 1));
+        assert_eq!(derive(50.0), (Duration::from_millis(20), 1));
+    }
+
+    #[test]
+    fn derives_bursts_above_the_single_click_rate() {
+        assert_eq!(derive(100.0), (Duration::from_millis(20), 2));
+        assert_eq!(derive(120.0), (Duration::from_millis(25), 3));
+        assert_eq!(derive(1e6), (Duration::from_micros(100), 100));
+    }
+
+    #[test]
+    fn derives_the_slowest_target() {
+        let (interval, clicks_count) = derive(parse_target("0.0000116").unwrap());
+
+        assert_eq!(clicks_count, 1);
+        assert!(interval <= Duration::from_secs(86_400));
+    }
+}
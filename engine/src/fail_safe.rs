@@ -0,0 +1,93 @@
+use crate::panic_key::PanicKey;
+use crate::stop_reason::StopReason;
+use enigo::{Enigo, Mouse};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How close (in pixels) the cursor has to get to a screen corner to trip the fail-safe.
+const CORNER_MARGIN: i32 = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScreenCorner {
+    #[default]
+    None,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ScreenCorner {
+    pub const ALL: [ScreenCorner; 5] = [
+        ScreenCorner::None,
+        ScreenCorner::TopLeft,
+        ScreenCorner::TopRight,
+        ScreenCorner::BottomLeft,
+        ScreenCorner::BottomRight,
+    ];
+
+    fn contains(&self, (x, y): (i32, i32), (width, height): (i32, i32)) -> bool {
+        let left = x <= CORNER_MARGIN;
+        let top = y <= CORNER_MARGIN;
+        let right = x >= width - 1 - CORNER_MARGIN;
+        let bottom = y >= height - 1 - CORNER_MARGIN;
+
+        match self {
+            ScreenCorner::None => false,
+            ScreenCorner::TopLeft => top && left,
+            ScreenCorner::TopRight => top && right,
+            ScreenCorner::BottomLeft => bottom && left,
+            ScreenCorner::BottomRight => bottom && right,
+        }
+    }
+}
+
+impl fmt::Display for ScreenCorner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ScreenCorner::None => "Off",
+            ScreenCorner::TopLeft => "Top left",
+            ScreenCorner::TopRight => "Top right",
+            ScreenCorner::BottomLeft => "Bottom left",
+            ScreenCorner::BottomRight => "Bottom right",
+        })
+    }
+}
+
+/// The fail-safes checked by the runner between every click. Only the panic key is on by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FailSafe {
+    pub corner: ScreenCorner,
+    /// Stop once the cursor drifts further than this from where the run started, `0` disables it.
+    pub mouse_move_threshold: u32,
+    pub panic_key: PanicKey,
+}
+
+impl FailSafe {
+    /// Returns the reason to stop if any of the cursor based fail-safes tripped, the panic key is
+    /// watched by the runner.
+    pub fn check(&self, enigo: &Enigo, origin: (i32, i32)) -> Option<StopReason> {
+        let Ok(location) = enigo.location() else {
+            return None;
+        };
+
+        if self.corner != ScreenCorner::None {
+            if let Ok(display) = enigo.main_display() {
+                if self.corner.contains(location, display) {
+                    return Some(StopReason::FailSafeCorner);
+                }
+            }
+        }
+
+        if self.mouse_move_threshold > 0 {
+            let dx = (location.0 - origin.0) as f64;
+            let dy = (location.1 - origin.1) as f64;
+
+            if dx.hypot(dy) > self.mouse_move_threshold as f64 {
+                return Some(StopReason::FailSafeMouseMove);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,18 @@
+//! The click engine of Click-R, shared by the window and the command line.
+//!
+//! A [`ClickJob`] describes what to click, a [`Schedule`] when, and a [`Runner`] clicks it on a
+//! thread of its own until a stop condition is met, reporting what happens as [`RunEvent`]s.
+
+pub mod click_config;
+pub mod cps;
+pub mod fail_safe;
+pub mod panic_key;
+pub mod pattern;
+pub mod runner;
+pub mod schedule;
+pub mod stop_reason;
+
+pub use click_config::ClickConfig;
+pub use runner::{ClickJob, RunEvent, RunHandle, RunProgress, Runner};
+pub use schedule::Schedule;
+pub use stop_reason::StopReason;
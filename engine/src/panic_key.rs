@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The key that stops a run, whichever window has the focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PanicKey {
+    None,
+    Escape,
+    #[default]
+    Pause,
+    F8,
+    F9,
+    F10,
+    F12,
+}
+
+impl PanicKey {
+    pub const ALL: [PanicKey; 7] = [
+        PanicKey::None,
+        PanicKey::Escape,
+        PanicKey::Pause,
+        PanicKey::F8,
+        PanicKey::F9,
+        PanicKey::F10,
+        PanicKey::F12,
+    ];
+}
+
+impl fmt::Display for PanicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PanicKey::None => f.write_str("Off"),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Reads whether the panic key is held down from the system rather than from a window, so the
+/// runner notices it while another application has the focus.
+pub(crate) struct KeyWatch(platform::Watch);
+
+impl KeyWatch {
+    /// `None` when the key is off or the keyboard state cannot be read, for example on Wayland.
+    pub fn new(key: PanicKey) -> Option<Self> {
+        if key == PanicKey::None {
+            return None;
+        }
+
+        platform::Watch::new(key).map(KeyWatch)
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.0.is_pressed()
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use super::PanicKey;
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ConnectionExt, Keycode};
+    use x11rb::rust_connection::RustConnection;
+
+    pub struct Watch {
+        connection: RustConnection,
+        keycodes: Vec<Keycode>,
+    }
+
+    impl Watch {
+        pub fn new(key: PanicKey) -> Option<Self> {
+            let keysym = match key {
+                PanicKey::None => return None,
+                PanicKey::Escape => 0xff1b,
+                PanicKey::Pause => 0xff13,
+                PanicKey::F8 => 0xffc5,
+                PanicKey::F9 => 0xffc6,
+                PanicKey::F10 => 0xffc7,
+                PanicKey::F12 => 0xffc9,
+            };
+
+            let (connection, _) = x11rb::connect(None).ok()?;
+            let setup = connection.setup();
+            let (first, count) = (setup.min_keycode, setup.max_keycode - setup.min_keycode + 1);
+            let mapping = connection
+                .get_keyboard_mapping(first, count)
+                .ok()?
+                .reply()
+                .ok()?;
+            let per_keycode = usize::from(mapping.keysyms_per_keycode.max(1));
+            let keycodes: Vec<Keycode> = mapping
+                .keysyms
+                .chunks(per_keycode)
+                .zip(first..=setup.max_keycode)
+                .filter(|(keysyms, _)| keysyms.contains(&keysym))
+                .map(|(_, keycode)| keycode)
+                .collect();
+
+            if keycodes.is_empty() {
+                return None;
+            }
+
+            Some(Watch {
+                connection,
+                keycodes,
+            })
+        }
+
+        pub fn is_pressed(&self) -> bool {
+            let Some(keymap) = self
+                .connection
+                .query_keymap()
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+            else {
+                return false;
+            };
+
+            self.keycodes
+                .iter()
+                .any(|&keycode| keymap.keys[usize::from(keycode / 8)] & (1 << (keycode % 8)) != 0)
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::PanicKey;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VK_ESCAPE, VK_F10, VK_F12, VK_F8, VK_F9, VK_PAUSE,
+    };
+
+    pub struct Watch(u16);
+
+    impl Watch {
+        pub fn new(key: PanicKey) -> Option<Self> {
+            Some(Watch(match key {
+                PanicKey::None => return None,
+                PanicKey::Escape => VK_ESCAPE,
+                PanicKey::Pause => VK_PAUSE,
+                PanicKey::F8 => VK_F8,
+                PanicKey::F9 => VK_F9,
+                PanicKey::F10 => VK_F10,
+                PanicKey::F12 => VK_F12,
+            }))
+        }
+
+        pub fn is_pressed(&self) -> bool {
+            // The most significant bit is set while the key is down.
+            unsafe { GetAsyncKeyState(i32::from(self.0)) < 0 }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::PanicKey;
+
+    /// `kCGEventSourceStateCombinedSessionState`, the keys held down on any keyboard.
+    const COMBINED_SESSION_STATE: i32 = 0;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceKeyState(state_id: i32, key: u16) -> bool;
+    }
+
+    pub struct Watch(u16);
+
+    impl Watch {
+        pub fn new(key: PanicKey) -> Option<Self> {
+            // Mac keyboards have no Pause key.
+            Some(Watch(match key {
+                PanicKey::None | PanicKey::Pause => return None,
+                PanicKey::Escape => 0x35,
+                PanicKey::F8 => 0x64,
+                PanicKey::F9 => 0x65,
+                PanicKey::F10 => 0x6d,
+                PanicKey::F12 => 0x6f,
+            }))
+        }
+
+        pub fn is_pressed(&self) -> bool {
+            unsafe { CGEventSourceKeyState(COMBINED_SESSION_STATE, self.0) }
+        }
+    }
+}
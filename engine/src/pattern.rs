@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhaseKind {
+    Work,
+    Rest,
+}
+
+impl PhaseKind {
+    pub const ALL: [PhaseKind; 2] = [PhaseKind::Work, PhaseKind::Rest];
+}
+
+impl fmt::Display for PhaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// One step of a burst pattern, rest phases ignore the interval and clicks count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Phase {
+    pub kind: PhaseKind,
+    pub length_seconds: u64,
+    pub interval_ms: u64,
+    pub clicks_count: u8,
+}
+
+impl Default for Phase {
+    fn default() -> Self {
+        Self {
+            kind: PhaseKind::Work,
+            length_seconds: 30,
+            interval_ms: 200,
+            clicks_count: 1,
+        }
+    }
+}
+
+/// Work/rest phases that replace the single interval while enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurstPattern {
+    pub enabled: bool,
+    pub phases: Vec<Phase>,
+    /// How many times the phases are played, `0` repeats them until another stop condition.
+    pub repeat: u64,
+}
+
+impl Default for BurstPattern {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            phases: vec![
+                Phase::default(),
+                Phase {
+                    kind: PhaseKind::Rest,
+                    length_seconds: 120,
+                    ..Phase::default()
+                },
+            ],
+            repeat: 10,
+        }
+    }
+}
+
+/// Where a patterned run currently is, shared with the view.
+#[derive(Debug, Clone, Copy)]
+pub struct PhaseStatus {
+    pub index: usize,
+    pub kind: PhaseKind,
+    pub round: u64,
+    pub ends_at: Instant,
+}
+
+/// Walks through the phases of a pattern as time passes.
+pub struct PhaseClock {
+    phases: Vec<Phase>,
+    repeat: u64,
+    index: usize,
+    round: u64,
+    ends_at: Instant,
+}
+
+impl PhaseClock {
+    /// Returns `None` when the pattern is disabled or has no time to play.
+    pub fn new(pattern: &BurstPattern, now: Instant) -> Option<Self> {
+        if !pattern.enabled || pattern.phases.iter().all(|phase| phase.length_seconds == 0) {
+            return None;
+        }
+
+        Some(Self {
+            phases: pattern.phases.clone(),
+            repeat: pattern.repeat,
+            index: 0,
+            round: 1,
+            ends_at: now + Duration::from_secs(pattern.phases[0].length_seconds),
+        })
+    }
+
+    /// Moves past every finished phase, returns `false` once the last round is over.
+    pub fn advance(&mut self, now: Instant) -> bool {
+        while now >= self.ends_at {
+            self.index += 1;
+
+            if self.index == self.phases.len() {
+                self.index = 0;
+                self.round += 1;
+
+                if self.repeat > 0 && self.round > self.repeat {
+                    return false;
+                }
+            }
+
+            self.ends_at += Duration::from_secs(self.phases[self.index].length_seconds);
+        }
+
+        true
+    }
+
+    /// Pushes the end of the current phase back, used to leave out the time a run was paused.
+    pub fn delay(&mut self, by: Duration) {
+        self.ends_at += by;
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phases[self.index]
+    }
+
+    pub fn ends_at(&self) -> Instant {
+        self.ends_at
+    }
+
+    pub fn status(&self) -> PhaseStatus {
+        PhaseStatus {
+            index: self.index,
+            kind: self.phase().kind,
+            round: self.round,
+            ends_at: self.ends_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(repeat: u64) -> BurstPattern {
+        BurstPattern {
+            enabled: true,
+            phases: vec![
+                Phase {
+                    length_seconds: 10,
+                    ..Phase::default()
+                },
+                Phase {
+                    kind: PhaseKind::Rest,
+                    length_seconds: 5,
+                    ..Phase::default()
+                },
+            ],
+            repeat,
+        }
+    }
+
+    fn seconds(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn needs_an_enabled_pattern_with_time_to_play() {
+        let now = Instant::now();
+
+        assert!(PhaseClock::new(&BurstPattern::default(), now).is_none());
+        assert!(PhaseClock::new(
+            &BurstPattern {
+                phases: Vec::new(),
+                ..pattern(1)
+            },
+            now
+        )
+        .is_none());
+
+        let mut silent = pattern(1);
+        silent
+            .phases
+            .iter_mut()
+            .for_each(|phase| phase.length_seconds = 0);
+        assert!(PhaseClock::new(&silent, now).is_none());
+    }
+
+    #[test]
+    fn walks_through_the_phases_and_rounds() {
+        let start = Instant::now();
+        let mut clock = PhaseClock::new(&pattern(0), start).unwrap();
+
+        assert_eq!((clock.status().index, clock.status().round), (0, 1));
+        assert_eq!(clock.ends_at(), start + seconds(10));
+
+        assert!(clock.advance(start + seconds(12)));
+        assert_eq!(clock.phase().kind, PhaseKind::Rest);
+        assert_eq!(clock.ends_at(), start + seconds(15));
+
+        // Phases that passed unseen are skipped over.
+        assert!(clock.advance(start + seconds(41)));
+        assert_eq!((clock.status().index, clock.status().round), (1, 3));
+        assert_eq!(clock.ends_at(), start + seconds(45));
+    }
+
+    #[test]
+    fn ends_after_the_last_round() {
+        let start = Instant::now();
+        let mut clock = PhaseClock::new(&pattern(2), start).unwrap();
+
+        assert!(clock.advance(start + seconds(29)));
+        assert_eq!((clock.status().index, clock.status().round), (1, 2));
+        assert!(!clock.advance(start + seconds(30)));
+    }
+
+    #[test]
+    fn puts_off_the_phase_end_by_a_delay() {
+        let start = Instant::now();
+        let mut clock = PhaseClock::new(&pattern(1), start).unwrap();
+
+        clock.delay(seconds(3));
+
+        assert!(clock.advance(start + seconds(12)));
+        assert_eq!(clock.phase().kind, PhaseKind::Work);
+        assert_eq!(clock.status().ends_at, start + seconds(13));
+    }
+}
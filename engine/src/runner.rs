@@ -0,0 +1,797 @@
+use crate::click_config::ClickConfig;
+use crate::fail_safe::FailSafe;
+use crate::panic_key::KeyWatch;
+use crate::pattern::{BurstPattern, PhaseClock, PhaseKind, PhaseStatus};
+use crate::schedule::Schedule;
+use crate::stop_reason::StopReason;
+use enigo::{Direction::Click, Enigo, Mouse, Settings as EnigoSettings};
+use std::sync::mpsc::{self, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the runner re-checks the fail-safes while waiting for the start or the next cycle, and
+/// the stop time while paused.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What to click and what ends the run besides a stop request and the schedule.
+#[derive(Debug, Clone, Default)]
+pub struct ClickJob {
+    /// The starting click settings, replace them during the run with [`RunHandle::set_clicks`].
+    pub clicks: ClickConfig,
+    pub burst_pattern: BurstPattern,
+    /// `None` runs until another stop condition is met.
+    pub duration: Option<Duration>,
+    /// `0` disables the limit, like `max_cycles`.
+    pub max_total_clicks: u64,
+    pub max_cycles: u64,
+    pub fail_safe: FailSafe,
+    /// Counts the clicks without sending them, the fail-safes are skipped if the mouse cannot be
+    /// read either.
+    pub dry_run: bool,
+}
+
+/// What happened during a run, in the order it happened.
+#[derive(Debug, Clone, Copy)]
+pub enum RunEvent {
+    /// The start delay is over and clicking begins.
+    Started,
+    PhaseChanged(PhaseStatus),
+    Paused,
+    Resumed,
+    /// Always the last event of a run.
+    Finished(StopReason),
+}
+
+/// What the runner reports while it runs, for views that poll instead of following the events.
+#[derive(Debug, Clone, Default)]
+pub struct RunProgress {
+    pub running: Arc<Mutex<bool>>,
+    pub paused: Arc<Mutex<bool>>,
+    pub total_clicks: Arc<Mutex<u64>>,
+    pub phase_status: Arc<Mutex<Option<PhaseStatus>>>,
+    /// When the first click was sent, moved forward by the time spent paused.
+    pub clicking_started_at: Arc<Mutex<Option<Instant>>>,
+    pub stop_reason: Arc<Mutex<Option<StopReason>>>,
+}
+
+enum Control {
+    Stop(StopReason),
+    Pause,
+    Resume,
+}
+
+type Subscribers = Arc<Mutex<Vec<mpsc::Sender<RunEvent>>>>;
+
+/// Controls a run from another thread, cheap to clone.
+///
+/// The run stops as [`StopReason::User`] once the runner and every handle to it are dropped.
+#[derive(Debug, Clone)]
+pub struct RunHandle {
+    control: mpsc::Sender<Control>,
+    clicks: Arc<Mutex<ClickConfig>>,
+    thread: thread::Thread,
+}
+
+impl RunHandle {
+    /// Holds the run until [`resume`](Self::resume), the duration, the phases and the wait for
+    /// the next cycle are all put off by the time spent paused.
+    pub fn pause(&self) {
+        self.send(Control::Pause);
+    }
+
+    pub fn resume(&self) {
+        self.send(Control::Resume);
+    }
+
+    /// Ends the run, a run that already ended keeps its reason.
+    pub fn stop(&self, reason: StopReason) {
+        self.send(Control::Stop(reason));
+    }
+
+    /// Replaces the click settings, they apply from the next cycle on.
+    pub fn set_clicks(&self, clicks: ClickConfig) {
+        *self.clicks.lock().unwrap() = clicks;
+        self.thread.unpark();
+    }
+
+    fn send(&self, control: Control) {
+        // Sending fails once the run ended, there is nothing left to control then.
+        let _ = self.control.send(control);
+        self.thread.unpark();
+    }
+}
+
+/// Clicks a job on a thread of its own.
+pub struct Runner {
+    handle: RunHandle,
+    progress: RunProgress,
+    subscribers: Subscribers,
+    thread: thread::JoinHandle<StopReason>,
+}
+
+impl Runner {
+    /// Starts the run right away, it waits on its own thread for `schedule.start_after`.
+    pub fn start(job: ClickJob, schedule: Schedule) -> Self {
+        let (control_sender, control) = mpsc::channel();
+        let clicks = Arc::new(Mutex::new(job.clicks));
+        let progress = RunProgress::default();
+        let subscribers = Subscribers::default();
+        *progress.running.lock().unwrap() = true;
+
+        let worker = Worker {
+            control,
+            clicks: Arc::clone(&clicks),
+            progress: progress.clone(),
+            subscribers: Arc::clone(&subscribers),
+        };
+
+        let thread = thread::spawn(move || {
+            let mut finish = Finish {
+                progress: worker.progress.clone(),
+                subscribers: Arc::clone(&worker.subscribers),
+                reason: StopReason::Crashed,
+            };
+            finish.reason = worker.run(job, schedule);
+            finish.reason
+        });
+
+        Self {
+            handle: RunHandle {
+                control: control_sender,
+                clicks,
+                thread: thread.thread().clone(),
+            },
+            progress,
+            subscribers,
+            thread,
+        }
+    }
+
+    pub fn handle(&self) -> RunHandle {
+        self.handle.clone()
+    }
+
+    pub fn pause(&self) {
+        self.handle.pause();
+    }
+
+    pub fn resume(&self) {
+        self.handle.resume();
+    }
+
+    pub fn stop(&self, reason: StopReason) {
+        self.handle.stop(reason);
+    }
+
+    pub fn set_clicks(&self, clicks: ClickConfig) {
+        self.handle.set_clicks(clicks);
+    }
+
+    /// The click settings the run uses from the next cycle on.
+    pub fn clicks(&self) -> ClickConfig {
+        *self.handle.clicks.lock().unwrap()
+    }
+
+    pub fn progress(&self) -> &RunProgress {
+        &self.progress
+    }
+
+    /// Reports every event from now on, a run that already ended reports only
+    /// [`RunEvent::Finished`].
+    pub fn subscribe(&self) -> mpsc::Receiver<RunEvent> {
+        let (sender, events) = mpsc::channel();
+        let mut subscribers = self.subscribers.lock().unwrap();
+
+        match *self.progress.stop_reason.lock().unwrap() {
+            Some(reason) => {
+                let _ = sender.send(RunEvent::Finished(reason));
+            }
+            None => subscribers.push(sender),
+        }
+
+        events
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Waits for the run to end, stop it first to not wait for its limits.
+    pub fn join(self) -> StopReason {
+        self.thread.join().unwrap_or(StopReason::Crashed)
+    }
+}
+
+/// Ends the run once the click thread exits, also when it panics, so that every subscriber hears
+/// of it and the runner lets go of them.
+struct Finish {
+    progress: RunProgress,
+    subscribers: Subscribers,
+    reason: StopReason,
+}
+
+impl Drop for Finish {
+    fn drop(&mut self) {
+        let mut subscribers = lock(&self.subscribers);
+        *lock(&self.progress.stop_reason) = Some(self.reason);
+        *lock(&self.progress.paused) = false;
+        *lock(&self.progress.running) = false;
+
+        for subscriber in subscribers.drain(..) {
+            let _ = subscriber.send(RunEvent::Finished(self.reason));
+        }
+    }
+}
+
+/// Locks `mutex` also after a panic on the click thread poisoned it, the value is still whole.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// The state of the click thread.
+struct Worker {
+    control: mpsc::Receiver<Control>,
+    clicks: Arc<Mutex<ClickConfig>>,
+    progress: RunProgress,
+    subscribers: Subscribers,
+}
+
+impl Worker {
+    fn emit(&self, event: RunEvent) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(event).is_ok());
+    }
+
+    /// Handles the control messages that arrived and waits out a pause.
+    ///
+    /// Returns how long the run was paused, or the reason to stop.
+    fn check_control(&self, stop_at: Option<Instant>) -> Result<Duration, StopReason> {
+        let mut paused_at = None;
+
+        loop {
+            // `Err(true)` means every handle is gone.
+            let control = match paused_at {
+                None => self
+                    .control
+                    .try_recv()
+                    .map_err(|error| error == TryRecvError::Disconnected),
+                Some(_) => self
+                    .control
+                    .recv_timeout(POLL_INTERVAL)
+                    .map_err(|error| error == RecvTimeoutError::Disconnected),
+            };
+
+            match (control, paused_at) {
+                (Ok(Control::Stop(reason)), _) => return Err(reason),
+                (Ok(Control::Pause), None) => {
+                    paused_at = Some(Instant::now());
+                    *self.progress.paused.lock().unwrap() = true;
+                    self.emit(RunEvent::Paused);
+                }
+                (Ok(Control::Resume), Some(paused_at)) => {
+                    *self.progress.paused.lock().unwrap() = false;
+                    self.emit(RunEvent::Resumed);
+                    return Ok(paused_at.elapsed());
+                }
+                (Ok(_), _) => {}
+                (Err(true), _) => return Err(StopReason::User),
+                (Err(false), None) => return Ok(Duration::ZERO),
+                (Err(false), Some(_)) => {
+                    if stop_at.is_some_and(|stop_at| Instant::now() >= stop_at) {
+                        return Err(StopReason::ScheduledStop);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Leaves the time spent paused out of every deadline, the cursor may have been moved in the
+    /// meantime so the mouse move fail-safe starts over from where it is now.
+    fn resume(
+        &self,
+        paused: Duration,
+        enigo: &Option<Enigo>,
+        end_time: &mut Option<Instant>,
+        phase_clock: &mut Option<PhaseClock>,
+        origin: &mut (i32, i32),
+    ) {
+        if paused.is_zero() {
+            return;
+        }
+
+        *end_time = end_time.and_then(|end_time| end_time.checked_add(paused));
+
+        if let Some(phase_clock) = phase_clock {
+            phase_clock.delay(paused);
+            *self.progress.phase_status.lock().unwrap() = Some(phase_clock.status());
+        }
+
+        if let Some(clicking_started_at) = &mut *self.progress.clicking_started_at.lock().unwrap() {
+            *clicking_started_at += paused;
+        }
+
+        *origin = cursor(enigo);
+    }
+
+    /// Clicks on the current thread until a stop condition is met or a stop request arrives.
+    fn run(&self, job: ClickJob, schedule: Schedule) -> StopReason {
+        let ClickJob {
+            clicks: _,
+            burst_pattern,
+            duration,
+            max_total_clicks,
+            max_cycles,
+            fail_safe,
+            dry_run,
+        } = job;
+        let Schedule {
+            start_after,
+            stop_at,
+        } = schedule;
+
+        let mut enigo = Enigo::new(&EnigoSettings::default()).ok();
+
+        if enigo.is_none() && !dry_run {
+            return StopReason::InputUnavailable;
+        }
+
+        let panic_key = KeyWatch::new(fail_safe.panic_key);
+        let check_fail_safe = |fail_safe: &FailSafe, enigo: &Option<Enigo>, origin| {
+            if panic_key.as_ref().is_some_and(KeyWatch::is_pressed) {
+                return Some(StopReason::PanicKey);
+            }
+
+            enigo
+                .as_ref()
+                .and_then(|enigo| fail_safe.check(enigo, origin))
+        };
+
+        // The cursor is still being moved into place during the start delay, so only the corner
+        // and the panic key can end the run before the first click.
+        let waiting_fail_safe = FailSafe {
+            mouse_move_threshold: 0,
+            ..fail_safe
+        };
+        // A start or an end too far ahead for `Instant` is as good as never.
+        let mut start_at = Instant::now().checked_add(start_after);
+        let until_start = |start_at: Option<Instant>| match start_at {
+            Some(start_at) => start_at.checked_duration_since(Instant::now()),
+            None => Some(POLL_INTERVAL),
+        };
+
+        while let Some(remaining) = until_start(start_at) {
+            match self.check_control(stop_at) {
+                Ok(paused) => start_at = start_at.and_then(|start_at| start_at.checked_add(paused)),
+                Err(reason) => return reason,
+            }
+
+            if let Some(reason) = check_fail_safe(&waiting_fail_safe, &enigo, (0, 0)) {
+                return reason;
+            }
+
+            thread::park_timeout(remaining.min(POLL_INTERVAL));
+        }
+
+        let mut end_time = duration.and_then(|duration| Instant::now().checked_add(duration));
+        let mut origin = cursor(&enigo);
+        *self.progress.clicking_started_at.lock().unwrap() = Some(Instant::now());
+        self.emit(RunEvent::Started);
+
+        let mut phase_clock = PhaseClock::new(&burst_pattern, Instant::now());
+        let mut last_phase = None;
+        let mut clicks = 0;
+        let mut cycles = 0;
+
+        'clicking: loop {
+            match self.check_control(stop_at) {
+                Ok(paused) => {
+                    self.resume(paused, &enigo, &mut end_time, &mut phase_clock, &mut origin)
+                }
+                Err(reason) => break reason,
+            }
+
+            if end_time.is_some_and(|end_time| Instant::now() >= end_time) {
+                break StopReason::Duration;
+            }
+
+            if stop_at.is_some_and(|stop_at| Instant::now() >= stop_at) {
+                break StopReason::ScheduledStop;
+            }
+
+            let config = *self.clicks.lock().unwrap();
+
+            let (cycle_interval, cycle_clicks_count) = match &mut phase_clock {
+                Some(phase_clock) => {
+                    if !phase_clock.advance(Instant::now()) {
+                        break StopReason::PatternComplete;
+                    }
+
+                    let status = phase_clock.status();
+                    *self.progress.phase_status.lock().unwrap() = Some(status);
+
+                    if last_phase != Some((status.index, status.round)) {
+                        last_phase = Some((status.index, status.round));
+                        self.emit(RunEvent::PhaseChanged(status));
+                    }
+
+                    let phase = phase_clock.phase();
+
+                    match phase.kind {
+                        PhaseKind::Work => {
+                            (Duration::from_millis(phase.interval_ms), phase.clicks_count)
+                        }
+                        PhaseKind::Rest => (Duration::ZERO, 0),
+                    }
+                }
+                None => (config.interval, config.clicks_count),
+            };
+
+            if cycle_clicks_count > 0 {
+                for _ in 0..cycle_clicks_count {
+                    if max_total_clicks > 0 && clicks >= max_total_clicks {
+                        break 'clicking StopReason::ClickLimit;
+                    }
+
+                    if let Some(reason) = check_fail_safe(&fail_safe, &enigo, origin) {
+                        break 'clicking reason;
+                    }
+
+                    if let Some(enigo) = enigo.as_mut().filter(|_| !dry_run) {
+                        if enigo.button(config.button, Click).is_err() {
+                            break 'clicking StopReason::InputUnavailable;
+                        }
+                    }
+
+                    clicks += 1;
+                    *self.progress.total_clicks.lock().unwrap() += 1;
+                }
+
+                cycles += 1;
+
+                if max_total_clicks > 0 && clicks >= max_total_clicks {
+                    break StopReason::ClickLimit;
+                }
+
+                if max_cycles > 0 && cycles >= max_cycles {
+                    break StopReason::CycleLimit;
+                }
+            }
+
+            let mut cycle_ended_at = Instant::now();
+            let mut next_cycle = cycle_ended_at + cycle_interval;
+
+            if let Some(phase_clock) = &phase_clock {
+                if cycle_clicks_count == 0 || phase_clock.ends_at() < next_cycle {
+                    next_cycle = phase_clock.ends_at();
+                }
+            }
+
+            while let Some(remaining) = next_cycle.checked_duration_since(Instant::now()) {
+                match self.check_control(stop_at) {
+                    Ok(paused) => {
+                        self.resume(paused, &enigo, &mut end_time, &mut phase_clock, &mut origin);
+                        cycle_ended_at += paused;
+                        next_cycle += paused;
+                    }
+                    Err(reason) => break 'clicking reason,
+                }
+
+                if let Some(reason) = check_fail_safe(&fail_safe, &enigo, origin) {
+                    break 'clicking reason;
+                }
+
+                if phase_clock.is_none() {
+                    next_cycle = cycle_ended_at + self.clicks.lock().unwrap().interval;
+                }
+
+                thread::park_timeout(remaining.min(POLL_INTERVAL));
+            }
+        }
+    }
+}
+
+fn cursor(enigo: &Option<Enigo>) -> (i32, i32) {
+    enigo
+        .as_ref()
+        .and_then(|enigo| enigo.location().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::Phase;
+
+    const FAST: Duration = Duration::from_millis(1);
+    /// Long enough that a run never gets to its second cycle during a test.
+    const SLOW: Duration = Duration::from_secs(3600);
+
+    fn job(interval: Duration) -> ClickJob {
+        ClickJob {
+            clicks: ClickConfig {
+                interval,
+                ..ClickConfig::default()
+            },
+            dry_run: true,
+            ..ClickJob::default()
+        }
+    }
+
+    fn clicks(runner: &Runner) -> u64 {
+        *runner.progress().total_clicks.lock().unwrap()
+    }
+
+    /// Waits for `condition`, failing the test after a few seconds.
+    fn wait_for(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while !condition() {
+            assert!(Instant::now() < deadline, "timed out");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn stops_at_the_click_limit() {
+        let runner = Runner::start(
+            ClickJob {
+                max_total_clicks: 5,
+                ..job(FAST)
+            },
+            Schedule::default(),
+        );
+        let events = runner.subscribe();
+
+        assert_eq!(runner.join(), StopReason::ClickLimit);
+        assert!(matches!(events.recv(), Ok(RunEvent::Started)));
+        assert!(matches!(
+            events.recv(),
+            Ok(RunEvent::Finished(StopReason::ClickLimit))
+        ));
+        assert!(events.recv().is_err());
+    }
+
+    #[test]
+    fn stops_at_the_cycle_limit() {
+        let mut job = job(FAST);
+        job.clicks.clicks_count = 3;
+        job.max_cycles = 2;
+        let runner = Runner::start(job, Schedule::default());
+        let progress = runner.progress().clone();
+
+        assert_eq!(runner.join(), StopReason::CycleLimit);
+        assert_eq!(*progress.total_clicks.lock().unwrap(), 6);
+        assert!(!*progress.running.lock().unwrap());
+        assert_eq!(
+            *progress.stop_reason.lock().unwrap(),
+            Some(StopReason::CycleLimit)
+        );
+    }
+
+    #[test]
+    fn stops_after_the_duration_and_the_start_delay() {
+        let started_at = Instant::now();
+        let runner = Runner::start(
+            ClickJob {
+                duration: Some(Duration::from_millis(100)),
+                ..job(FAST)
+            },
+            Schedule {
+                start_after: Duration::from_millis(100),
+                stop_at: None,
+            },
+        );
+
+        assert_eq!(runner.join(), StopReason::Duration);
+        assert!(started_at.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn stops_at_the_scheduled_stop_time() {
+        let runner = Runner::start(
+            job(FAST),
+            Schedule {
+                start_after: Duration::ZERO,
+                stop_at: Some(Instant::now() + Duration::from_millis(100)),
+            },
+        );
+
+        assert_eq!(runner.join(), StopReason::ScheduledStop);
+    }
+
+    #[test]
+    fn stops_with_the_reason_it_was_given() {
+        let runner = Runner::start(job(SLOW), Schedule::default());
+        wait_for(|| clicks(&runner) == 1);
+
+        runner.stop(StopReason::PanicKey);
+        runner.stop(StopReason::User);
+
+        assert_eq!(runner.join(), StopReason::PanicKey);
+    }
+
+    #[test]
+    fn stops_during_the_start_delay() {
+        let runner = Runner::start(
+            job(FAST),
+            Schedule {
+                start_after: SLOW,
+                stop_at: None,
+            },
+        );
+
+        runner.stop(StopReason::User);
+
+        assert_eq!(runner.join(), StopReason::User);
+    }
+
+    #[test]
+    fn stops_once_every_handle_is_gone() {
+        let runner = Runner::start(job(SLOW), Schedule::default());
+        let events = runner.subscribe();
+        drop(runner);
+
+        let finished = events.iter().last();
+        assert!(matches!(
+            finished,
+            Some(RunEvent::Finished(StopReason::User))
+        ));
+    }
+
+    #[test]
+    fn holds_the_clicks_and_the_duration_while_paused() {
+        let runner = Runner::start(
+            ClickJob {
+                duration: Some(Duration::from_millis(200)),
+                ..job(FAST)
+            },
+            Schedule::default(),
+        );
+        let events = runner.subscribe();
+        let started_at = Instant::now();
+
+        runner.pause();
+        wait_for(|| *runner.progress().paused.lock().unwrap());
+        let paused_clicks = clicks(&runner);
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(clicks(&runner), paused_clicks);
+
+        runner.resume();
+        assert_eq!(runner.join(), StopReason::Duration);
+        assert!(started_at.elapsed() >= Duration::from_millis(500));
+
+        let events: Vec<_> = events.iter().collect();
+        assert!(matches!(
+            events
+                .iter()
+                .find(|event| !matches!(event, RunEvent::Started)),
+            Some(RunEvent::Paused)
+        ));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, RunEvent::Resumed)));
+    }
+
+    #[test]
+    fn stops_at_the_scheduled_stop_time_while_paused() {
+        let runner = Runner::start(
+            job(FAST),
+            Schedule {
+                start_after: Duration::ZERO,
+                stop_at: Some(Instant::now() + Duration::from_millis(100)),
+            },
+        );
+
+        runner.pause();
+
+        assert_eq!(runner.join(), StopReason::ScheduledStop);
+    }
+
+    #[test]
+    fn applies_new_click_settings_from_the_next_cycle() {
+        let runner = Runner::start(
+            ClickJob {
+                max_total_clicks: 4,
+                ..job(SLOW)
+            },
+            Schedule::default(),
+        );
+        wait_for(|| clicks(&runner) == 1);
+
+        let faster = ClickConfig {
+            interval: FAST,
+            ..runner.clicks()
+        };
+        runner.set_clicks(faster);
+
+        assert_eq!(runner.clicks(), faster);
+        assert_eq!(runner.join(), StopReason::ClickLimit);
+    }
+
+    #[test]
+    fn finishes_a_pattern() {
+        let runner = Runner::start(
+            ClickJob {
+                burst_pattern: BurstPattern {
+                    enabled: true,
+                    phases: vec![Phase {
+                        length_seconds: 1,
+                        interval_ms: 100,
+                        ..Phase::default()
+                    }],
+                    repeat: 1,
+                },
+                ..job(SLOW)
+            },
+            Schedule::default(),
+        );
+        let events = runner.subscribe();
+        let progress = runner.progress().clone();
+
+        assert_eq!(runner.join(), StopReason::PatternComplete);
+        assert!(*progress.total_clicks.lock().unwrap() >= 5);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, RunEvent::PhaseChanged(status) if status.index == 0)));
+    }
+
+    #[test]
+    fn reports_a_finished_run_to_late_subscribers() {
+        let runner = Runner::start(
+            ClickJob {
+                max_total_clicks: 1,
+                ..job(FAST)
+            },
+            Schedule::default(),
+        );
+        wait_for(|| runner.is_finished());
+
+        let events: Vec<_> = runner.subscribe().iter().collect();
+
+        assert!(matches!(
+            events[..],
+            [RunEvent::Finished(StopReason::ClickLimit)]
+        ));
+    }
+
+    #[test]
+    fn finishes_the_run_when_the_click_thread_panics() {
+        let progress = RunProgress::default();
+        let subscribers = Subscribers::default();
+        let (sender, events) = mpsc::channel();
+        subscribers.lock().unwrap().push(sender);
+        *progress.running.lock().unwrap() = true;
+
+        let finish = Finish {
+            progress: progress.clone(),
+            subscribers: Arc::clone(&subscribers),
+            reason: StopReason::Crashed,
+        };
+        let poison = Arc::clone(&subscribers);
+        let panicked = thread::spawn(move || {
+            let _finish = finish;
+            let _subscribers = poison.lock().unwrap();
+            panic!("click thread failed");
+        })
+        .join();
+
+        assert!(panicked.is_err());
+        assert!(matches!(
+            events.recv(),
+            Ok(RunEvent::Finished(StopReason::Crashed))
+        ));
+        assert!(events.recv().is_err());
+        assert!(subscribers.lock().unwrap().is_empty());
+        assert!(!*progress.running.lock().unwrap());
+        assert_eq!(
+            *progress.stop_reason.lock().unwrap(),
+            Some(StopReason::Crashed)
+        );
+    }
+}
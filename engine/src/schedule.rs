@@ -0,0 +1,144 @@
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use croner::errors::CronError;
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+pub const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+const DATE_TIME_INPUT_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+const TIME_INPUT_FORMATS: [&str; 2] = ["%H:%M:%S", "%H:%M"];
+
+/// Parses a local wall-clock time such as `2026-10-20 09:00:00` or `09:00`.
+///
+/// A time without a date refers to its next occurrence, so `09:00` typed in the evening means
+/// tomorrow morning.
+pub fn parse_local_date_time(input: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let input = input.trim();
+
+    if let Some(date_time) = DATE_TIME_INPUT_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+    {
+        return date_time.and_local_timezone(Local).earliest();
+    }
+
+    let time = TIME_INPUT_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(input, format).ok())?;
+    let today = at_local(now.date_naive(), time)?;
+
+    if today > now {
+        Some(today)
+    } else {
+        at_local(now.date_naive() + ChronoDuration::days(1), time)
+    }
+}
+
+/// Why the scheduled start and stop times cannot be used for a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleError {
+    InvalidStart,
+    StartPassed,
+    InvalidStop,
+    StopPassed,
+    StopNotAfterStart,
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ScheduleError::InvalidStart => "The start time is not a valid date or time",
+            ScheduleError::StartPassed => "The start time has already passed",
+            ScheduleError::InvalidStop => "The stop time is not a valid date or time",
+            ScheduleError::StopPassed => "The stop time has already passed",
+            ScheduleError::StopNotAfterStart => "The stop time is not after the start time",
+        })
+    }
+}
+
+/// The resolved start and stop times of a run, `None` where none was given.
+pub type StartStop = (Option<DateTime<Local>>, Option<DateTime<Local>>);
+
+/// Resolves the scheduled start and stop of a run, either may be left empty.
+///
+/// A stop time without a date refers to its next occurrence after the start, so "start
+/// 2026-10-21 09:00, stop 17:00" ends the same afternoon.
+pub fn resolve_start_stop(
+    start: &str,
+    stop: &str,
+    now: DateTime<Local>,
+) -> Result<StartStop, ScheduleError> {
+    let start = match start.trim() {
+        "" => None,
+        input => {
+            let start = parse_local_date_time(input, now).ok_or(ScheduleError::InvalidStart)?;
+
+            if start <= now {
+                return Err(ScheduleError::StartPassed);
+            }
+
+            Some(start)
+        }
+    };
+
+    let stop = match stop.trim() {
+        "" => None,
+        input => {
+            let after = start.unwrap_or(now);
+            let stop = parse_local_date_time(input, after).ok_or(ScheduleError::InvalidStop)?;
+
+            if stop <= now {
+                return Err(ScheduleError::StopPassed);
+            }
+
+            if stop <= after {
+                return Err(ScheduleError::StopNotAfterStart);
+            }
+
+            Some(stop)
+        }
+    };
+
+    Ok((start, stop))
+}
+
+/// Time left from `now` until `target`, zero if it is already in the past.
+pub fn until(target: DateTime<Local>, now: DateTime<Local>) -> Duration {
+    (target - now).to_std().unwrap_or_default()
+}
+
+fn at_local(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    date.and_time(time).and_local_timezone(Local).earliest()
+}
+
+/// When a run handed to the runner begins and when it is cut short at the latest.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Schedule {
+    pub start_after: Duration,
+    /// `None` leaves ending the run to the limits of the job.
+    pub stop_at: Option<Instant>,
+}
+
+/// A run triggered automatically whenever its cron expression matches, e.g. `55 8 * * MON-FRI`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurringSchedule {
+    pub expression: String,
+    /// How long each triggered run lasts, `0` falls back to the configured duration.
+    pub run_minutes: u64,
+}
+
+impl RecurringSchedule {
+    pub fn next_run_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        parse_cron(&self.expression)
+            .ok()?
+            .find_next_occurrence(&after, false)
+            .ok()
+    }
+}
+
+/// Parses a five field cron expression, an optional leading seconds field is accepted too.
+pub fn parse_cron(expression: &str) -> Result<Cron, CronError> {
+    Cron::new(expression.trim()).with_seconds_optional().parse()
+}
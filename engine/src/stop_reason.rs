@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// Records which condition ended the last run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    User,
+    PanicKey,
+    Duration,
+    ScheduledStop,
+    ClickLimit,
+    CycleLimit,
+    PatternComplete,
+    FailSafeCorner,
+    FailSafeMouseMove,
+    /// The mouse could not be controlled, for example without a display to connect to.
+    InputUnavailable,
+    /// The click thread panicked.
+    Crashed,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StopReason::User => "Stopped by user",
+            StopReason::PanicKey => "Panic key pressed",
+            StopReason::Duration => "Duration elapsed",
+            StopReason::ScheduledStop => "Scheduled stop time reached",
+            StopReason::ClickLimit => "Click limit reached",
+            StopReason::CycleLimit => "Cycle limit reached",
+            StopReason::PatternComplete => "Pattern finished",
+            StopReason::FailSafeCorner => "Cursor hit the fail-safe corner",
+            StopReason::FailSafeMouseMove => "Mouse moved during the run",
+            StopReason::InputUnavailable => "Unable to control the mouse",
+            StopReason::Crashed => "The click thread crashed",
+        })
+    }
+}
@@ -0,0 +1,385 @@
+use crate::settings::CollisionResolution;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use click_r_engine::fail_safe::ScreenCorner;
+use enigo::Button as MouseButton;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Read and write settings from this file instead of the user config directory
+    #[arg(long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Keep settings next to the executable, also enabled by a `portable` file placed there
+    #[arg(long, conflicts_with = "config", global = true)]
+    pub portable: bool,
+
+    #[command(flatten)]
+    pub launch: LaunchFlags,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Parses the command line like [`Parser::parse`], and refuses launch flags next to a
+    /// subcommand, which would ignore them.
+    pub fn parse_args() -> Self {
+        let mut command = Self::command();
+        let matches = command.get_matches_mut();
+
+        if let Some((subcommand, _)) = matches.subcommand() {
+            let launch_flags = LaunchFlags::augment_args(clap::Command::new("launch"));
+            let given = launch_flags.get_arguments().find(|flag| {
+                matches.value_source(flag.get_id().as_str()) == Some(ValueSource::CommandLine)
+            });
+
+            if let Some(flag) = given {
+                command
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        format!(
+                            "the subcommand '{}' cannot be used with '--{}'",
+                            subcommand,
+                            flag.get_long().unwrap_or_default()
+                        ),
+                    )
+                    .exit();
+            }
+        }
+
+        Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit())
+    }
+}
+
+/// How the window opens, passed on as the flags of the application. When a window is open already
+/// they are forwarded to it instead.
+#[derive(Args, Default)]
+pub struct LaunchFlags {
+    /// Open with this profile selected
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Begin the run as soon as the window opens
+    #[arg(long, conflicts_with = "toggle")]
+    pub start: bool,
+
+    /// Begin a run, or stop the one in progress, for example from a window manager keybinding
+    #[arg(long)]
+    pub toggle: bool,
+
+    /// Open the window minimized
+    #[arg(long)]
+    pub minimized: bool,
+
+    /// Go through runs without clicking, to try out a setup
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Serve a remote control page and API over HTTP, on localhost unless an address is given
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        num_args = 0..=1,
+        default_missing_value = DEFAULT_HTTP_ADDRESS
+    )]
+    pub http: Option<SocketAddr>,
+
+    /// Require this token from HTTP clients, needed to serve on an address other than localhost
+    #[arg(long, value_name = "TOKEN", env = "CLICK_R_HTTP_TOKEN")]
+    pub http_token: Option<String>,
+}
+
+pub const DEFAULT_HTTP_ADDRESS: &str = "127.0.0.1:8421";
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Click without opening the window until a limit is reached or Ctrl+C is pressed
+    Run(RunArgs),
+    /// List, show, export and import profiles
+    #[command(subcommand)]
+    Profile(ProfileCommand),
+    /// Inspect settings files
+    #[command(subcommand)]
+    Settings(SettingsCommand),
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommand {
+    /// List every profile, the active one is marked with `*`
+    List,
+    /// Print the values of a profile as JSON
+    Show {
+        /// Defaults to the active profile
+        name: Option<String>,
+    },
+    /// Export profiles to a bundle file that `profile import` and the window can read
+    Export {
+        /// Defaults to the active profile
+        names: Vec<String>,
+        /// Export every profile
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+        /// Write the bundle here instead of to stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Import the profiles of a bundle file into the settings
+    Import {
+        file: PathBuf,
+        /// What to do with profiles whose name is already taken, by default the import is refused
+        #[arg(long, value_enum)]
+        on_conflict: Option<ConflictArg>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SettingsCommand {
+    /// Print the settings file in use
+    Path,
+    /// Check that a settings file can be loaded, exits with 1 if not
+    Validate { file: PathBuf },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ConflictArg {
+    /// Import them as `Name (2)`, `Name (3)`, ...
+    Rename,
+    Overwrite,
+}
+
+impl From<ConflictArg> for CollisionResolution {
+    fn from(conflict: ConflictArg) -> Self {
+        match conflict {
+            ConflictArg::Rename => CollisionResolution::Rename,
+            ConflictArg::Overwrite => CollisionResolution::Overwrite,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Time between cycles, e.g. `150ms`, `2s` or `1m30s`
+    #[arg(long, default_value = "1s", value_parser = parse_interval)]
+    pub interval: Duration,
+
+    /// Clicks per cycle
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..))]
+    pub count: u8,
+
+    #[arg(long, value_enum, default_value_t = ButtonArg::Left)]
+    pub button: ButtonArg,
+
+    /// Wait this long before the first click
+    #[arg(long, default_value = "0s", value_parser = parse_duration)]
+    pub delay: Duration,
+
+    /// Stop after clicking for this long
+    #[arg(long, value_parser = parse_run_duration)]
+    pub duration: Option<Duration>,
+
+    /// Stop after this many clicks
+    #[arg(long, value_name = "CLICKS")]
+    pub max_clicks: Option<u64>,
+
+    /// Stop after this many cycles
+    #[arg(long, value_name = "CYCLES")]
+    pub max_cycles: Option<u64>,
+
+    /// Stop once the cursor reaches this screen corner
+    #[arg(long, value_enum, value_name = "CORNER")]
+    pub fail_safe_corner: Option<CornerArg>,
+
+    /// Stop once the cursor moves further than this many pixels from where the run started
+    #[arg(long, value_name = "PIXELS")]
+    pub max_mouse_move: Option<u32>,
+
+    /// Only print the result, not the progress every second
+    #[arg(long, short)]
+    pub quiet: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ButtonArg {
+    Left,
+    Right,
+    Middle,
+}
+
+impl From<ButtonArg> for MouseButton {
+    fn from(button: ButtonArg) -> Self {
+        match button {
+            ButtonArg::Left => MouseButton::Left,
+            ButtonArg::Right => MouseButton::Right,
+            ButtonArg::Middle => MouseButton::Middle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CornerArg {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl From<CornerArg> for ScreenCorner {
+    fn from(corner: CornerArg) -> Self {
+        match corner {
+            CornerArg::TopLeft => ScreenCorner::TopLeft,
+            CornerArg::TopRight => ScreenCorner::TopRight,
+            CornerArg::BottomLeft => ScreenCorner::BottomLeft,
+            CornerArg::BottomRight => ScreenCorner::BottomRight,
+        }
+    }
+}
+
+/// Parses a duration like [`parse_duration`] that is not zero, a zero interval never stops
+/// clicking long enough for anything else to run.
+pub fn parse_interval(input: &str) -> Result<Duration, String> {
+    match parse_duration(input)? {
+        Duration::ZERO => Err("the interval must be longer than 0".to_string()),
+        interval => Ok(interval),
+    }
+}
+
+/// Parses a run length like [`parse_duration`] that is not zero, a zero duration would end the
+/// run before its first click.
+pub fn parse_run_duration(input: &str) -> Result<Duration, String> {
+    match parse_duration(input)? {
+        Duration::ZERO => Err("the duration must be longer than 0".to_string()),
+        duration => Ok(duration),
+    }
+}
+
+/// Parses durations such as `150ms`, `5s`, `10m`, `2h` or `1m30s`, a bare number means seconds.
+///
+/// Durations that reach further ahead than the clock can count are rejected.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err("a duration cannot be empty".to_string());
+    }
+
+    let too_long = || format!("\"{}\" is too long", input);
+    let representable = |duration: Duration| {
+        Instant::now()
+            .checked_add(duration)
+            .map(|_| duration)
+            .ok_or_else(too_long)
+    };
+
+    if let Ok(seconds) = input.parse::<u64>() {
+        return representable(Duration::from_secs(seconds));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit_length = rest[digits..]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len() - digits);
+
+        let value: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| format!("\"{}\" is not a duration like 150ms, 5s or 1m30s", input))?;
+
+        let part = match &rest[digits..digits + unit_length] {
+            "ms" => Duration::from_millis(value),
+            "s" => Duration::from_secs(value),
+            "m" => Duration::from_secs(value.checked_mul(60).ok_or_else(too_long)?),
+            "h" => Duration::from_secs(value.checked_mul(3600).ok_or_else(too_long)?),
+            unit => {
+                return Err(format!(
+                    "unknown unit \"{}\" in \"{}\", use ms, s, m or h",
+                    unit, input
+                ))
+            }
+        };
+
+        total = total.checked_add(part).ok_or_else(too_long)?;
+        rest = &rest[digits + unit_length..];
+    }
+
+    representable(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_bare_number_as_seconds() {
+        assert_eq!(parse_duration("5"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_duration(" 0 "), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn parses_every_unit() {
+        assert_eq!(parse_duration("150ms"), Ok(Duration::from_millis(150)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+    }
+
+    #[test]
+    fn adds_up_combined_units() {
+        assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(
+            parse_duration("1h1m1s500ms"),
+            Ok(Duration::from_millis(3_661_500))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("1.5s").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn rejects_durations_that_overflow() {
+        assert!(parse_duration(&format!("{}m", u64::MAX)).is_err());
+        assert!(parse_duration(&format!("{}h", u64::MAX / 60)).is_err());
+        assert!(parse_duration(&format!("{}s{}s", u64::MAX, u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn rejects_durations_beyond_the_clock() {
+        assert_eq!(
+            parse_duration("18446744073709551615"),
+            Err("\"18446744073709551615\" is too long".to_string())
+        );
+        assert!(parse_duration(&format!("{}s", u64::MAX)).is_err());
+        assert!(parse_duration(&format!("{}ms", u64::MAX)).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_zero_run_duration() {
+        assert!(parse_run_duration("0").is_err());
+        assert!(parse_run_duration("0h0m").is_err());
+        assert_eq!(parse_run_duration("90"), Ok(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn rejects_a_zero_interval() {
+        assert!(parse_interval("0").is_err());
+        assert!(parse_interval("0ms").is_err());
+        assert!(parse_interval("0m0s").is_err());
+        assert_eq!(parse_interval("1ms"), Ok(Duration::from_millis(1)));
+    }
+}
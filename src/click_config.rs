@@ -0,0 +1,22 @@
+use crate::AutoClicker;
+use click_r_engine::cps;
+use click_r_engine::ClickConfig;
+use std::time::Duration;
+
+impl From<&AutoClicker> for ClickConfig {
+    fn from(auto_clicker: &AutoClicker) -> Self {
+        let (interval, clicks_count) = match cps::parse_target(&auto_clicker.profile.target_cps) {
+            Some(target_cps) if auto_clicker.profile.cps_mode => cps::derive(target_cps),
+            _ => (
+                Duration::from_secs(auto_clicker.profile.click_interval_slider_value as u64),
+                auto_clicker.profile.clicks_count_slider_value,
+            ),
+        };
+
+        Self {
+            interval,
+            clicks_count,
+            button: *auto_clicker.profile.selected_mouse_button.lock().unwrap(),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::cli::{LaunchFlags, ProfileCommand, SettingsCommand};
+use crate::settings::{self, CollisionResolution, ProfileBundle, SettingsFile};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::process::ExitCode;
+
+/// Runs a `profile` subcommand against the same settings file the window uses. Profiles that do
+/// not load are listed, shown and exported as they are stored, with a warning.
+pub fn profile(command: ProfileCommand) -> ExitCode {
+    let settings = match read_settings() {
+        Ok(settings) => settings,
+        Err(error) => return fail(error),
+    };
+
+    for error in settings.profile_errors(settings::path()) {
+        eprintln!("Warning: {}", error);
+    }
+
+    match command {
+        ProfileCommand::List => {
+            for name in settings.profiles.keys() {
+                let marker = if *name == settings.active_profile {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{} {}", marker, name);
+            }
+        }
+        ProfileCommand::Show { name } => {
+            let name = name.unwrap_or_else(|| settings.active_profile.clone());
+
+            match settings.profiles.get(&name) {
+                Some(profile) => println!("{}", serde_json::to_string_pretty(profile).unwrap()),
+                None => return fail(format!("There is no profile called \"{}\"", name)),
+            }
+        }
+        ProfileCommand::Export { names, all, output } => {
+            let names = match (all, names.is_empty()) {
+                (true, _) => settings.profiles.keys().cloned().collect(),
+                (false, true) => vec![settings.active_profile.clone()],
+                (false, false) => names,
+            };
+
+            let mut profiles = BTreeMap::new();
+
+            for name in names {
+                match settings.profiles.get(&name) {
+                    Some(profile) => profiles.insert(name, profile.clone()),
+                    None => return fail(format!("There is no profile called \"{}\"", name)),
+                };
+            }
+
+            let bundle = ProfileBundle::new(profiles, &settings.custom_themes);
+
+            match output {
+                Some(output) => {
+                    if let Err(error) = bundle.save(&output) {
+                        return fail(error);
+                    }
+                }
+                None => println!("{}", serde_json::to_string_pretty(&bundle).unwrap()),
+            }
+        }
+        ProfileCommand::Import { file, on_conflict } => {
+            let mut settings = settings;
+
+            let bundle = match ProfileBundle::load(&file) {
+                Ok(bundle) => bundle,
+                Err(error) => return fail(error),
+            };
+
+            let collisions = bundle.collisions(&settings.profiles);
+
+            if !collisions.is_empty() && on_conflict.is_none() {
+                return fail(format!(
+                    "These profiles already exist: {}. Pass --on-conflict rename or \
+                     --on-conflict overwrite to import them anyway",
+                    collisions.join(", ")
+                ));
+            }
+
+            let resolution = on_conflict.map_or(CollisionResolution::Rename, Into::into);
+            let imported = bundle.merge_into(
+                &mut settings.profiles,
+                &mut settings.custom_themes,
+                resolution,
+            );
+
+            if let Err(error) = settings.save() {
+                return fail(error);
+            }
+
+            for name in imported {
+                println!("{}", name);
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+pub fn settings(command: SettingsCommand) -> ExitCode {
+    match command {
+        SettingsCommand::Path => println!("{}", settings::path().display()),
+        SettingsCommand::Validate { file } => match SettingsFile::validate(&file) {
+            Ok(settings) => println!(
+                "{} is valid: {} profiles, \"{}\" active",
+                file.display(),
+                settings.profiles.len(),
+                settings.active_profile
+            ),
+            Err(error) => return fail(error),
+        },
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Refuses to open the window with a profile that does not exist. Settings that cannot be read
+/// are left to the window, which reports them.
+pub fn check_launch_flags(flags: &LaunchFlags) -> Result<(), String> {
+    if let Some(address) = flags.http {
+        if !address.ip().is_loopback() && flags.http_token.is_none() {
+            return Err(format!(
+                "Serving on {} lets other machines control the mouse, set --http-token as well",
+                address
+            ));
+        }
+    }
+
+    match (&flags.profile, read_settings()) {
+        (Some(profile), Ok(settings)) if !settings.profiles.contains_key(profile) => Err(format!(
+            "There is no profile called \"{}\", see `click-r profile list`",
+            profile
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the settings without backing up or otherwise touching an unusable file, and with the
+/// profiles the window would add on its first start. Profiles that do not load are kept.
+fn read_settings() -> Result<SettingsFile, settings::SettingsError> {
+    let mut settings = if settings::path().exists() {
+        SettingsFile::inspect(settings::path())?
+    } else {
+        SettingsFile::default()
+    };
+
+    settings.ensure_profiles();
+    Ok(settings)
+}
+
+fn fail(error: impl Display) -> ExitCode {
+    eprintln!("{}", error);
+    ExitCode::FAILURE
+}
@@ -0,0 +1,373 @@
+use super::{Connection, ControlCommand, ControlRequest};
+use crate::message::Message;
+use iced::futures::channel::{mpsc as futures_mpsc, oneshot};
+use iced::futures::{future, SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+use serde_json::Value;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+use zbus::{fdo, interface, SignalContext};
+
+const BUS_NAME: &str = "org.clickr.Clicker";
+const OBJECT_PATH: &str = "/org/clickr/Clicker";
+/// How long a method call waits for the window to answer.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn subscription() -> Subscription<Message> {
+    struct DBusService;
+
+    subscription::channel(
+        std::any::TypeId::of::<DBusService>(),
+        10,
+        |mut output| async move {
+            let (sender, mut requests) = futures_mpsc::unbounded();
+
+            let registered = match zbus::connection::Builder::session() {
+                Ok(bus) => serve(bus, sender.clone()).await,
+                Err(error) => Err(error),
+            };
+
+            match registered {
+                Ok(connection) => {
+                    let forward = async {
+                        while let Some(request) = requests.next().await {
+                            let _ = output.send(Message::Control(request)).await;
+                        }
+                    };
+                    let state_updates = async {
+                        if let Err(error) = watch(&connection, sender).await {
+                            eprintln!("Stopped updating the D-Bus state: {}", error);
+                        }
+                    };
+
+                    future::join(forward, state_updates).await;
+                }
+                Err(error) => eprintln!(
+                    "Unable to register {} on the session bus: {}",
+                    BUS_NAME, error
+                ),
+            }
+
+            future::pending().await
+        },
+    )
+}
+
+/// Registers the clicker object on the bus that `bus` connects to, method calls are sent to
+/// `requests`.
+async fn serve(
+    bus: zbus::connection::Builder<'_>,
+    requests: futures_mpsc::UnboundedSender<ControlRequest>,
+) -> zbus::Result<zbus::Connection> {
+    let clicker = Clicker {
+        requests,
+        state: Mutex::new(String::from("idle")),
+    };
+
+    bus.name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, clicker)?
+        .build()
+        .await
+}
+
+/// Subscribes to the events of the window to keep `State` current and to send `RunFinished`.
+async fn watch(
+    connection: &zbus::Connection,
+    requests: futures_mpsc::UnboundedSender<ControlRequest>,
+) -> zbus::Result<()> {
+    let (sender, lines) = mpsc::channel();
+    let _ = requests.unbounded_send(ControlRequest {
+        command: ControlCommand::Subscribe,
+        connection: Connection(sender),
+    });
+
+    // The events arrive on a blocking channel, a thread passes them on without holding up the bus.
+    let (forward, mut events) = futures_mpsc::unbounded::<Value>();
+    thread::spawn(move || {
+        for line in lines {
+            if forward.unbounded_send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let clicker = connection
+        .object_server()
+        .interface::<_, Clicker>(OBJECT_PATH)
+        .await?;
+
+    while let Some(event) = events.next().await {
+        let context = clicker.signal_context();
+
+        match event["event"].as_str() {
+            Some("state") => {
+                let state = event["state"].as_str().unwrap_or_default();
+                let clicker = clicker.get().await;
+
+                if clicker.set_state(state) {
+                    clicker.state_changed(context).await?;
+                }
+            }
+            Some("finished") => {
+                let reason = event["reason"].as_str().unwrap_or_default();
+                Clicker::run_finished(context, reason).await?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Hands `command` to the window and waits for its answer, or for the reason it was refused.
+async fn ask(
+    requests: &futures_mpsc::UnboundedSender<ControlRequest>,
+    command: ControlCommand,
+) -> Result<Value, String> {
+    let (sender, answers) = mpsc::channel();
+    let request = ControlRequest {
+        command,
+        connection: Connection(sender),
+    };
+
+    if requests.unbounded_send(request).is_err() {
+        return Err(String::from("Click-R is shutting down"));
+    }
+
+    // The answer arrives on a blocking channel, waiting for it must not hold up the bus.
+    let (answer_sender, answer) = oneshot::channel();
+    thread::spawn(move || {
+        let _ = answer_sender.send(answers.recv_timeout(ANSWER_TIMEOUT));
+    });
+
+    match answer.await {
+        Ok(Ok(answer)) if answer["ok"] == true => Ok(answer),
+        Ok(Ok(answer)) => Err(answer["error"].as_str().unwrap_or_default().to_string()),
+        _ => Err(String::from("Click-R did not answer")),
+    }
+}
+
+struct Clicker {
+    requests: futures_mpsc::UnboundedSender<ControlRequest>,
+    /// The state as last announced by the window. The methods only take the interface for
+    /// reading while they wait for an answer, so `watch` updates the state through a lock of
+    /// its own.
+    state: Mutex<String>,
+}
+
+impl Clicker {
+    /// Hands `command` to the window, the state it leads to is announced by [`watch`].
+    async fn call(&self, command: ControlCommand) -> fdo::Result<()> {
+        ask(&self.requests, command)
+            .await
+            .map(|_| ())
+            .map_err(fdo::Error::Failed)
+    }
+
+    /// Returns whether `state` is a change.
+    fn set_state(&self, state: &str) -> bool {
+        let mut current = self.state.lock().unwrap();
+
+        if *current == state {
+            return false;
+        }
+
+        *current = state.to_string();
+        true
+    }
+}
+
+#[interface(name = "org.clickr.Clicker")]
+impl Clicker {
+    async fn start(&self) -> fdo::Result<()> {
+        self.call(ControlCommand::Start).await
+    }
+
+    async fn stop(&self) -> fdo::Result<()> {
+        self.call(ControlCommand::Stop).await
+    }
+
+    /// Starts a run, or stops the one in progress.
+    async fn toggle(&self) -> fdo::Result<()> {
+        self.call(ControlCommand::Toggle).await
+    }
+
+    async fn pause(&self) -> fdo::Result<()> {
+        self.call(ControlCommand::Pause).await
+    }
+
+    async fn resume(&self) -> fdo::Result<()> {
+        self.call(ControlCommand::Resume).await
+    }
+
+    async fn load_profile(&self, name: String) -> fdo::Result<()> {
+        self.call(ControlCommand::LoadProfile { name }).await
+    }
+
+    /// `idle`, `waiting`, `running` or `paused`.
+    #[zbus(property)]
+    async fn state(&self) -> String {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Sent when a run ends, with the reason it stopped.
+    #[zbus(signal)]
+    async fn run_finished(context: &SignalContext<'_>, reason: &str) -> zbus::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::{state_event, RunState};
+    use iced::futures::executor::block_on;
+    use serde_json::json;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use zbus::blocking::fdo::PropertiesProxy;
+    use zbus::blocking::Proxy;
+    use zbus::names::InterfaceName;
+
+    /// A session bus of its own, stopped when dropped.
+    struct SessionBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl SessionBus {
+        fn start() -> Self {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .expect("dbus-daemon must be installed to run this test");
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.as_mut().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+
+            SessionBus {
+                daemon,
+                address: address.trim().to_string(),
+            }
+        }
+    }
+
+    impl Drop for SessionBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    /// Stands in for the window: answers the calls and announces the states they lead to.
+    fn window(mut requests: futures_mpsc::UnboundedReceiver<ControlRequest>) {
+        let mut subscribers: Vec<Connection> = Vec::new();
+        let announce = |subscribers: &[Connection], event: Value| {
+            for subscriber in subscribers {
+                subscriber.send(event.clone());
+            }
+        };
+
+        while let Some(ControlRequest {
+            command,
+            connection,
+        }) = block_on(requests.next())
+        {
+            match command {
+                ControlCommand::Subscribe => {
+                    connection.ok();
+                    connection.send(state_event(RunState::Idle));
+                    subscribers.push(connection);
+                }
+                ControlCommand::Start => {
+                    connection.ok();
+                    announce(&subscribers, state_event(RunState::Running));
+                }
+                ControlCommand::Stop => {
+                    connection.ok();
+                    announce(&subscribers, state_event(RunState::Idle));
+                    announce(
+                        &subscribers,
+                        json!({ "event": "finished", "reason": "Stopped by user" }),
+                    );
+                }
+                ControlCommand::LoadProfile { name } => {
+                    connection.error(format!("No profile named \"{}\"", name));
+                }
+                _ => connection.ok(),
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with `cargo test -- --ignored`"]
+    fn serves_the_clicker_on_the_session_bus() {
+        let bus = SessionBus::start();
+        let server = zbus::connection::Builder::address(bus.address.as_str()).unwrap();
+
+        let (sender, requests) = futures_mpsc::unbounded();
+        thread::spawn(move || window(requests));
+
+        let (registered, registration) = mpsc::channel();
+        thread::spawn(move || {
+            block_on(async {
+                let connection = serve(server, sender.clone()).await.unwrap();
+                registered.send(()).unwrap();
+                watch(&connection, sender).await
+            })
+        });
+        registration.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let client = zbus::blocking::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .build()
+            .unwrap();
+        let clicker = Proxy::new(&client, BUS_NAME, OBJECT_PATH, BUS_NAME).unwrap();
+        let properties = PropertiesProxy::builder(&client)
+            .destination(BUS_NAME)
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut changes = properties.receive_properties_changed().unwrap();
+        let mut finished = clicker.receive_signal("RunFinished").unwrap();
+
+        clicker.call_method("Start", &()).unwrap();
+
+        let change = changes.next().unwrap();
+        let args = change.args().unwrap();
+        assert_eq!(
+            args.interface_name(),
+            &InterfaceName::from_static_str_unchecked(BUS_NAME)
+        );
+        assert_eq!(
+            args.changed_properties()["State"],
+            zbus::zvariant::Value::from("running")
+        );
+        let state: String = properties
+            .get(InterfaceName::from_static_str_unchecked(BUS_NAME), "State")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(state, "running");
+
+        clicker.call_method("Stop", &()).unwrap();
+
+        let reason: String = finished.next().unwrap().body().deserialize().unwrap();
+        assert_eq!(reason, "Stopped by user");
+
+        let error = clicker
+            .call_method("LoadProfile", &("Farming",))
+            .unwrap_err();
+        assert!(
+            matches!(&error, zbus::Error::MethodError(name, Some(message), _)
+                if name.as_str() == "org.freedesktop.DBus.Error.Failed"
+                    && message == "No profile named \"Farming\""),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,739 @@
+use super::{Connection, ControlCommand, ControlRequest};
+use crate::message::Message;
+use iced::futures::channel::mpsc as futures_mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message as WsMessage, WebSocket};
+
+const PAGE: &str = include_str!("page.html");
+/// Request bodies only ever hold a profile name, anything larger is refused.
+const MAX_BODY_LENGTH: usize = 16 * 1024;
+/// The request line and headers together, a browser sends far less.
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+const MAX_HEADERS: usize = 64;
+/// Connections beyond this many at a time, WebSockets included, are closed right away.
+const MAX_CONNECTIONS: usize = 32;
+const ROUTES: [&str; 8] = [
+    "/api/events",
+    "/api/status",
+    "/api/start",
+    "/api/stop",
+    "/api/toggle",
+    "/api/pause",
+    "/api/resume",
+    "/api/profile",
+];
+/// How long a client may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a request waits for the window to answer.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often a WebSocket connection checks for answers and events to send while the client is
+/// quiet.
+const WEBSOCKET_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+struct Server {
+    listener: TcpListener,
+    token: Option<String>,
+}
+
+/// The server bound at launch, served once the window subscribes to it.
+static SERVER: Mutex<Option<Server>> = Mutex::new(None);
+
+/// Binds the remote control server, requests are answered once the window is open.
+pub fn listen(address: SocketAddr, token: Option<String>) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    *SERVER.lock().unwrap() = Some(Server { listener, token });
+    Ok(())
+}
+
+pub fn subscription() -> Subscription<Message> {
+    struct HttpServer;
+
+    subscription::channel(
+        std::any::TypeId::of::<HttpServer>(),
+        10,
+        |mut output| async move {
+            let (sender, mut requests) = futures_mpsc::unbounded();
+
+            if let Some(server) = SERVER.lock().unwrap().take() {
+                thread::spawn(move || accept(server, sender));
+            }
+
+            loop {
+                let Some(request) = requests.next().await else {
+                    iced::futures::future::pending::<()>().await;
+                    continue;
+                };
+
+                let _ = output.send(Message::Control(request)).await;
+            }
+        },
+    )
+}
+
+fn accept(server: Server, requests: futures_mpsc::UnboundedSender<ControlRequest>) {
+    let open_connections = Arc::new(AtomicUsize::new(0));
+
+    for stream in server.listener.incoming().flatten() {
+        if open_connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            open_connections.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+
+        let open_connections = Arc::clone(&open_connections);
+        let token = server.token.clone();
+        let requests = requests.clone();
+
+        thread::spawn(move || {
+            if let Err(error) = serve(stream, token.as_deref(), &requests) {
+                // Clients that hang up early are not worth reporting.
+                if error.kind() != ErrorKind::BrokenPipe {
+                    eprintln!("Remote control request failed: {}", error);
+                }
+            }
+
+            open_connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    /// Header names are lowercase.
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn read(reader: &mut impl BufRead) -> io::Result<Self> {
+        let invalid = |reason: &str| io::Error::new(ErrorKind::InvalidData, reason.to_string());
+
+        // Everything up to the body is read through `head`, which ends after `MAX_HEADER_BYTES`.
+        let mut head = reader.take(MAX_HEADER_BYTES);
+        let mut read_line = |line: &mut String| match head.read_line(line)? {
+            _ if !line.ends_with('\n') && head.limit() == 0 => {
+                Err(invalid("the request headers are too large"))
+            }
+            read => Ok(read),
+        };
+
+        let mut request_line = String::new();
+        read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(invalid("malformed request line"));
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut headers = HashMap::new();
+
+        for count in 0.. {
+            let mut line = String::new();
+
+            if read_line(&mut line)? == 0 {
+                return Err(invalid("the request ended inside the headers"));
+            }
+
+            let line = line.trim_end();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if count == MAX_HEADERS {
+                return Err(invalid("the request has too many headers"));
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let length = match headers.get("content-length") {
+            Some(length) => length
+                .parse()
+                .map_err(|_| invalid("invalid Content-Length"))?,
+            None => 0,
+        };
+
+        if length > MAX_BODY_LENGTH {
+            return Err(invalid("the request body is too large"));
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(name, value)| (name.to_string(), percent_decode(value)))
+                .collect(),
+            headers,
+            body,
+        })
+    }
+
+    /// The token from an `Authorization: Bearer` header, or from the `token` query parameter for
+    /// browsers that cannot set headers, such as on WebSocket connections.
+    fn token(&self) -> Option<&str> {
+        self.headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or(self.query.get("token").map(String::as_str))
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    token: Option<&str>,
+    requests: &futures_mpsc::UnboundedSender<ControlRequest>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let request = match Request::read(&mut reader) {
+        Ok(request) => request,
+        Err(error) if error.kind() == ErrorKind::InvalidData => {
+            return respond_json(
+                &stream,
+                400,
+                &json!({ "ok": false, "error": error.to_string() }),
+            );
+        }
+        Err(error) => return Err(error),
+    };
+
+    // The page holds no secrets, it asks for the token itself.
+    if request.method == "GET" && request.path == "/" {
+        return respond(&stream, 200, "text/html; charset=utf-8", PAGE.as_bytes());
+    }
+
+    if !same_origin(&request, token.is_some()) {
+        return respond_json(
+            &stream,
+            403,
+            &json!({ "ok": false, "error": "Requests from other sites are not accepted" }),
+        );
+    }
+
+    let authorized = match token {
+        Some(token) => request
+            .token()
+            .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes())),
+        None => true,
+    };
+
+    if !authorized {
+        return respond_json(
+            &stream,
+            401,
+            &json!({ "ok": false, "error": "Missing or wrong token" }),
+        );
+    }
+
+    let command = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/events") => return serve_websocket(stream, &request, requests),
+        ("GET", "/api/status") => Ok(ControlCommand::Status),
+        ("POST", "/api/start") => Ok(ControlCommand::Start),
+        ("POST", "/api/stop") => Ok(ControlCommand::Stop),
+        ("POST", "/api/toggle") => Ok(ControlCommand::Toggle),
+        ("POST", "/api/pause") => Ok(ControlCommand::Pause),
+        ("POST", "/api/resume") => Ok(ControlCommand::Resume),
+        ("POST", "/api/profile") => serde_json::from_slice::<Value>(&request.body)
+            .ok()
+            .and_then(|body| Some(body.get("name")?.as_str()?.to_string()))
+            .map(|name| ControlCommand::LoadProfile { name })
+            .ok_or((400, "Expected a body like {\"name\": \"Default\"}")),
+        (_, path) if ROUTES.contains(&path) => Err((405, "Method not allowed")),
+        _ => Err((404, "Not found")),
+    };
+
+    let command = match command {
+        Ok(command) => command,
+        Err((status, error)) => {
+            return respond_json(&stream, status, &json!({ "ok": false, "error": error }));
+        }
+    };
+
+    let (sender, answers) = mpsc::channel();
+    let request = ControlRequest {
+        command,
+        connection: Connection(sender),
+    };
+
+    let (status, answer) = match requests.unbounded_send(request) {
+        Ok(()) => match answers.recv_timeout(ANSWER_TIMEOUT) {
+            Ok(answer) if answer["ok"] == true => (200, answer),
+            Ok(answer) => (409, answer),
+            Err(_) => (
+                503,
+                json!({ "ok": false, "error": "Click-R did not answer" }),
+            ),
+        },
+        Err(_) => (
+            503,
+            json!({ "ok": false, "error": "Click-R is shutting down" }),
+        ),
+    };
+
+    respond_json(&stream, status, &answer)
+}
+
+/// Takes commands as text messages and writes the answers and, after a `subscribe` command, the
+/// run events back, all in the JSON of the control socket.
+fn serve_websocket(
+    stream: TcpStream,
+    request: &Request,
+    requests: &futures_mpsc::UnboundedSender<ControlRequest>,
+) -> io::Result<()> {
+    let key = match request.headers.get("sec-websocket-key") {
+        Some(key)
+            if request
+                .headers
+                .get("upgrade")
+                .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket")) =>
+        {
+            key
+        }
+        _ => {
+            return respond_json(
+                &stream,
+                400,
+                &json!({ "ok": false, "error": "Expected a WebSocket upgrade" }),
+            );
+        }
+    };
+
+    write!(
+        &stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+
+    stream.set_read_timeout(Some(WEBSOCKET_POLL_INTERVAL))?;
+    let mut websocket = WebSocket::from_raw_socket(stream, Role::Server, None);
+    let (sender, lines) = mpsc::channel();
+    let connection = Connection(sender);
+
+    loop {
+        while let Ok(line) = lines.try_recv() {
+            websocket
+                .send(WsMessage::Text(line.to_string()))
+                .map_err(websocket_error)?;
+        }
+
+        let text = match websocket.read() {
+            Ok(WsMessage::Text(text)) => text,
+            Ok(WsMessage::Close(_)) => return Ok(()),
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(error))
+                if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                continue;
+            }
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(error) => return Err(websocket_error(error)),
+        };
+
+        match serde_json::from_str::<ControlCommand>(&text) {
+            Ok(command) => {
+                let request = ControlRequest {
+                    command,
+                    connection: connection.clone(),
+                };
+
+                if requests.unbounded_send(request).is_err() {
+                    connection.error("Click-R is shutting down");
+                }
+            }
+            Err(error) => connection.error(format!("Invalid command: {}", error)),
+        }
+    }
+}
+
+fn websocket_error(error: tungstenite::Error) -> io::Error {
+    match error {
+        tungstenite::Error::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+fn respond_json(stream: &TcpStream, status: u16, body: &Value) -> io::Result<()> {
+    respond(
+        stream,
+        status,
+        "application/json",
+        body.to_string().as_bytes(),
+    )
+}
+
+fn respond(mut stream: &TcpStream, status: u16, content_type: &str, body: &[u8]) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Service Unavailable",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\
+         Connection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// Refuses requests that web pages on other sites make through the browser of the user.
+///
+/// Browsers name the page in the `Origin` header, it has to be the control page itself. Without a
+/// token the host has to be localhost too, so a site that points its own name at `127.0.0.1`
+/// cannot pass for the control page.
+fn same_origin(request: &Request, has_token: bool) -> bool {
+    let host = request
+        .headers
+        .get("host")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let origin_matches = request
+        .headers
+        .get("origin")
+        .is_none_or(|origin| origin.strip_prefix("http://") == Some(host));
+    let hostname = match host.rsplit_once(':') {
+        Some((hostname, port)) if port.bytes().all(|byte| byte.is_ascii_digit()) => hostname,
+        _ => host,
+    };
+
+    let is_localhost = hostname == "localhost"
+        || hostname
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+
+    origin_matches && (has_token || is_localhost)
+}
+
+/// Compares tokens without giving away through timing how much of a guess was right.
+fn constant_time_eq(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+fn percent_decode(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                };
+
+                bytes.extend(decoded.map_or_else(
+                    || {
+                        [b'%']
+                            .into_iter()
+                            .chain(hex.into_iter().flatten())
+                            .collect()
+                    },
+                    |decoded| vec![decoded],
+                ));
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::time::Instant;
+
+    fn read(raw: &str) -> io::Result<Request> {
+        Request::read(&mut Cursor::new(raw.as_bytes()))
+    }
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: String::from("GET"),
+            path: String::from("/api/status"),
+            query: HashMap::new(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Sends `raw` to `serve` and returns the status it answered with. A request that reaches the
+    /// window gets `answer` back.
+    fn status(raw: &str, token: Option<&str>, answer: Value) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, mut requests) = futures_mpsc::unbounded::<ControlRequest>();
+        let token = token.map(String::from);
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve(stream, token.as_deref(), &sender)
+        });
+
+        let mut client = TcpStream::connect(address).unwrap();
+        client.write_all(raw.as_bytes()).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+
+        while !server.is_finished() && Instant::now() < deadline {
+            if let Ok(Some(request)) = requests.try_next() {
+                request.connection.send(answer.clone());
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        server.join().unwrap().unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        response[9..12].parse().unwrap()
+    }
+
+    #[test]
+    fn reads_a_request() {
+        let request = read(
+            "POST /api/profile?token=a%20b&x=1 HTTP/1.1\r\nHost: localhost:8421\r\n\
+             Content-Type: application/json\r\nContent-Length: 17\r\n\r\n{\"name\": \"Farm\"}\n",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/profile");
+        assert_eq!(request.query["token"], "a b");
+        assert_eq!(request.query["x"], "1");
+        assert_eq!(request.headers["host"], "localhost:8421");
+        assert_eq!(request.headers["content-type"], "application/json");
+        assert_eq!(request.body, b"{\"name\": \"Farm\"}\n");
+        assert_eq!(request.token(), Some("a b"));
+    }
+
+    #[test]
+    fn prefers_the_authorization_header() {
+        let request =
+            read("GET /api/status?token=query HTTP/1.1\r\nAuthorization: Bearer header\r\n\r\n")
+                .unwrap();
+
+        assert_eq!(request.token(), Some("header"));
+    }
+
+    #[test]
+    fn refuses_malformed_requests() {
+        for raw in [
+            "",
+            "GET\r\n\r\n",
+            "GET / HTTP/1.1\r\nHost: localhost\r\n",
+            "POST /api/profile HTTP/1.1\r\nContent-Length: many\r\n\r\n",
+        ] {
+            let error = read(raw).err().unwrap();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn refuses_oversized_requests() {
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(MAX_HEADER_BYTES as usize)
+        );
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-Header: a\r\n".repeat(MAX_HEADERS + 1)
+        );
+        let large_body = format!(
+            "POST /api/profile HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_LENGTH + 1
+        );
+
+        for raw in [long_header, many_headers, large_body] {
+            assert_eq!(read(&raw).err().unwrap().kind(), ErrorKind::InvalidData);
+        }
+
+        let headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-Header: a\r\n".repeat(MAX_HEADERS)
+        );
+        assert!(read(&headers).is_ok());
+    }
+
+    #[test]
+    fn accepts_requests_from_the_control_page() {
+        assert!(same_origin(&request(&[("host", "localhost:8421")]), false));
+        assert!(same_origin(
+            &request(&[
+                ("host", "127.0.0.1:8421"),
+                ("origin", "http://127.0.0.1:8421")
+            ]),
+            false
+        ));
+        assert!(same_origin(&request(&[("host", "[::1]:8421")]), false));
+        assert!(same_origin(&request(&[("host", "192.168.1.2:8421")]), true));
+    }
+
+    #[test]
+    fn refuses_requests_from_other_sites() {
+        assert!(!same_origin(
+            &request(&[
+                ("host", "localhost:8421"),
+                ("origin", "http://evil.example")
+            ]),
+            false
+        ));
+        assert!(!same_origin(
+            &request(&[("host", "evil.example:8421")]),
+            false
+        ));
+        assert!(!same_origin(
+            &request(&[
+                ("host", "192.168.1.2:8421"),
+                ("origin", "https://192.168.1.2:8421")
+            ]),
+            true
+        ));
+    }
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(percent_decode("a%20b+c"), "a b c");
+        assert_eq!(percent_decode("%C3%A9t%C3%A9"), "été");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[test]
+    fn answers_with_the_status_of_the_route() {
+        let ok = json!({ "ok": true });
+
+        assert_eq!(status("GET / HTTP/1.1\r\n\r\n", None, ok.clone()), 200);
+        assert_eq!(
+            status(
+                "GET /api/status HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                None,
+                ok.clone()
+            ),
+            200
+        );
+        assert_eq!(
+            status(
+                "GET /nothing HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                None,
+                ok.clone()
+            ),
+            404
+        );
+        assert_eq!(
+            status(
+                "GET /api/start HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                None,
+                ok.clone()
+            ),
+            405
+        );
+        assert_eq!(
+            status(
+                "POST /api/profile HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\n\r\n{}",
+                None,
+                ok
+            ),
+            400
+        );
+    }
+
+    #[test]
+    fn answers_409_when_the_window_refuses() {
+        let refused = json!({ "ok": false, "error": "A run is in progress already" });
+
+        assert_eq!(
+            status(
+                "POST /api/start HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                None,
+                refused
+            ),
+            409
+        );
+    }
+
+    #[test]
+    fn checks_the_origin_and_the_token() {
+        let ok = json!({ "ok": true });
+        let token = Some("secret");
+
+        assert_eq!(
+            status(
+                "POST /api/start HTTP/1.1\r\nHost: localhost\r\nOrigin: http://evil.example\r\n\r\n",
+                None,
+                ok.clone()
+            ),
+            403
+        );
+        assert_eq!(
+            status(
+                "POST /api/start HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                token,
+                ok.clone()
+            ),
+            401
+        );
+        assert_eq!(
+            status(
+                "POST /api/start HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer wrong\r\n\r\n",
+                token,
+                ok.clone()
+            ),
+            401
+        );
+        assert_eq!(
+            status(
+                "POST /api/start HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer secret\r\n\r\n",
+                token,
+                ok
+            ),
+            200
+        );
+    }
+}
@@ -0,0 +1,255 @@
+#[cfg(target_os = "linux")]
+mod dbus;
+pub mod http;
+#[cfg(unix)]
+mod socket;
+
+use crate::cli::LaunchFlags;
+use crate::message::Message;
+use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::RunEvent;
+use iced::Subscription;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::mpsc;
+
+/// A command read from a control connection, one JSON object per line such as
+/// `{"command": "load-profile", "name": "Farming"}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum ControlCommand {
+    Start,
+    Stop,
+    /// Starts a run, or stops the one in progress.
+    Toggle,
+    Pause,
+    Resume,
+    LoadProfile {
+        name: String,
+    },
+    /// Brings the window to the front.
+    Show,
+    Status,
+    /// Keeps the connection open and writes every run event to it from now on.
+    Subscribe,
+}
+
+impl ControlCommand {
+    /// The message the window sends for the same action, `None` for the commands that only read.
+    pub fn message(&self) -> Option<Message> {
+        match self {
+            ControlCommand::Start => Some(Message::Start),
+            ControlCommand::Stop => Some(Message::Stop),
+            ControlCommand::Toggle => Some(Message::Toggle),
+            ControlCommand::Pause => Some(Message::Pause),
+            ControlCommand::Resume => Some(Message::Resume),
+            ControlCommand::LoadProfile { name } => Some(Message::LoadSessionProfile(name.clone())),
+            ControlCommand::Show => Some(Message::RaiseWindow),
+            ControlCommand::Status | ControlCommand::Subscribe => None,
+        }
+    }
+}
+
+/// Where the answers and events for one control connection are written to.
+#[derive(Debug, Clone)]
+pub struct Connection(mpsc::Sender<Value>);
+
+impl Connection {
+    /// Returns `false` once the other end has disconnected.
+    pub fn send(&self, line: Value) -> bool {
+        self.0.send(line).is_ok()
+    }
+
+    pub fn ok(&self) {
+        self.send(json!({ "ok": true }));
+    }
+
+    pub fn error(&self, error: impl fmt::Display) {
+        self.send(json!({ "ok": false, "error": error.to_string() }));
+    }
+}
+
+/// A command together with the connection it came from.
+#[derive(Debug, Clone)]
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub connection: Connection,
+}
+
+/// What the clicker is doing, as reported by `status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunState {
+    #[default]
+    Idle,
+    /// Waiting for the start delay or the scheduled start.
+    Waiting,
+    Running,
+    Paused,
+}
+
+impl RunState {
+    pub fn of(auto_clicker: &AutoClicker) -> Self {
+        if !*auto_clicker.is_running.lock().unwrap() {
+            RunState::Idle
+        } else if *auto_clicker.is_paused.lock().unwrap() {
+            RunState::Paused
+        } else if auto_clicker
+            .run_starts_at
+            .is_some_and(|run_starts_at| Local::now() < run_starts_at)
+        {
+            RunState::Waiting
+        } else {
+            RunState::Running
+        }
+    }
+}
+
+impl fmt::Display for RunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RunState::Idle => "idle",
+            RunState::Waiting => "waiting",
+            RunState::Running => "running",
+            RunState::Paused => "paused",
+        })
+    }
+}
+
+pub fn status(auto_clicker: &AutoClicker) -> Value {
+    json!({
+        "state": RunState::of(auto_clicker).to_string(),
+        "profile": auto_clicker.active_profile,
+        "profiles": auto_clicker.profiles.keys().collect::<Vec<_>>(),
+        "total_clicks": *auto_clicker.total_clicks.lock().unwrap(),
+        "seconds_running": auto_clicker.time_running,
+        "last_stop_reason": auto_clicker
+            .stop_reason
+            .lock()
+            .unwrap()
+            .map(|reason| reason.to_string()),
+        "dry_run": auto_clicker.dry_run,
+    })
+}
+
+/// Tells the subscribers about the state `status` reports whenever it changed since they were last
+/// told, so they do not have to ask for it over and over.
+pub fn announce_state(auto_clicker: &mut AutoClicker) {
+    let state = RunState::of(auto_clicker);
+
+    if state != auto_clicker.announced_state {
+        auto_clicker.announced_state = state;
+        auto_clicker
+            .control_subscribers
+            .retain(|connection| connection.send(state_event(state)));
+    }
+}
+
+pub fn state_event(state: RunState) -> Value {
+    json!({ "event": "state", "state": state.to_string() })
+}
+
+pub fn event(event: RunEvent) -> Value {
+    match event {
+        RunEvent::Started => json!({ "event": "started" }),
+        RunEvent::PhaseChanged(status) => json!({
+            "event": "phase-changed",
+            "phase": status.index,
+            "kind": status.kind.to_string().to_lowercase(),
+            "round": status.round,
+        }),
+        RunEvent::Paused => json!({ "event": "paused" }),
+        RunEvent::Resumed => json!({ "event": "resumed" }),
+        RunEvent::Finished(reason) => json!({
+            "event": "finished",
+            "reason": reason.to_string(),
+        }),
+    }
+}
+
+/// `click-r.sock` in the per-user runtime directory, or in a directory named after the user id
+/// in the temporary directory where there is none.
+#[cfg(unix)]
+pub fn socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(|| std::env::temp_dir().join(format!("click-r-{}", socket::current_uid())))
+        .join("click-r.sock")
+}
+
+/// The commands a second launch sends to the window that is open already.
+fn forwarded_commands(launch: &LaunchFlags) -> Vec<ControlCommand> {
+    let mut commands = Vec::new();
+
+    if let Some(name) = &launch.profile {
+        commands.push(ControlCommand::LoadProfile { name: name.clone() });
+    }
+
+    if launch.start {
+        commands.push(ControlCommand::Start);
+    } else if launch.toggle {
+        commands.push(ControlCommand::Toggle);
+    } else if !launch.minimized {
+        commands.push(ControlCommand::Show);
+    }
+
+    commands
+}
+
+/// Makes this process the one instance that owns the control socket, or forwards the launch flags
+/// to the instance that does and returns the exit code to end with.
+#[cfg(unix)]
+pub fn claim_or_forward(launch: &LaunchFlags) -> Option<ExitCode> {
+    socket::claim_or_forward(launch)
+}
+
+/// Without a control socket the launch flags cannot reach the instance that is open already, a
+/// lock file held while the window is open only keeps a second one from opening.
+#[cfg(not(unix))]
+pub fn claim_or_forward(_launch: &LaunchFlags) -> Option<ExitCode> {
+    static LOCK: std::sync::Mutex<Option<std::fs::File>> = std::sync::Mutex::new(None);
+
+    let path = std::env::temp_dir().join("click-r.lock");
+    let locked = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map(|file| {
+            let locked = file.try_lock();
+            (file, locked)
+        });
+
+    match locked {
+        Ok((file, Ok(()))) => {
+            *LOCK.lock().unwrap() = Some(file);
+            None
+        }
+        Ok((_, Err(std::fs::TryLockError::WouldBlock))) => {
+            eprintln!("Click-R is running already");
+            Some(ExitCode::FAILURE)
+        }
+        Ok((_, Err(std::fs::TryLockError::Error(error)))) | Err(error) => {
+            eprintln!(
+                "Unable to lock {}, more than one window may open: {}",
+                path.display(),
+                error
+            );
+            None
+        }
+    }
+}
+
+/// Serves the control socket, see [`socket_path`], the D-Bus service on Linux and the HTTP server
+/// if one was bound at launch.
+pub fn subscription() -> Subscription<Message> {
+    Subscription::batch([
+        #[cfg(unix)]
+        socket::subscription(),
+        #[cfg(target_os = "linux")]
+        dbus::subscription(),
+        http::subscription(),
+    ])
+}
@@ -0,0 +1,268 @@
+use super::{forwarded_commands, socket_path, Connection, ControlCommand, ControlRequest};
+use crate::cli::LaunchFlags;
+use crate::message::Message;
+use iced::futures::channel::mpsc as futures_mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+use serde_json::Value;
+use std::fs::{self, DirBuilder, File};
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// The listener claimed at launch, served once the window subscribes to it.
+static LISTENER: Mutex<Option<UnixListener>> = Mutex::new(None);
+
+pub fn claim_or_forward(launch: &LaunchFlags) -> Option<ExitCode> {
+    let path = socket_path();
+
+    match secure_directory(&path).and_then(|()| claim(&path)) {
+        Ok(Instance::First(listener)) => {
+            *LISTENER.lock().unwrap() = Some(listener);
+            None
+        }
+        Ok(Instance::Running(stream)) => Some(forward(stream, launch)),
+        Err(error) => {
+            eprintln!(
+                "Unable to listen on {}, other programs cannot control this window: {}",
+                path.display(),
+                error
+            );
+            None
+        }
+    }
+}
+
+pub fn subscription() -> Subscription<Message> {
+    struct ControlSocket;
+
+    subscription::channel(
+        std::any::TypeId::of::<ControlSocket>(),
+        10,
+        |mut output| async move {
+            let (sender, mut requests) = futures_mpsc::unbounded();
+
+            if let Some(listener) = LISTENER.lock().unwrap().take() {
+                thread::spawn(move || accept(listener, sender));
+            }
+
+            loop {
+                let Some(request) = requests.next().await else {
+                    iced::futures::future::pending::<()>().await;
+                    continue;
+                };
+
+                let _ = output.send(Message::Control(request)).await;
+            }
+        },
+    )
+}
+
+pub fn current_uid() -> u32 {
+    // SAFETY: `geteuid` cannot fail and touches no memory of ours.
+    unsafe { libc::geteuid() }
+}
+
+/// The user on the other end of `stream`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+
+    // SAFETY: `credentials` and `length` outlive the call and `length` holds the size of
+    // `credentials`.
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut credentials as *mut libc::ucred as *mut libc::c_void,
+            &mut length,
+        )
+    };
+
+    match result {
+        0 => Ok(credentials.uid),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// The user on the other end of `stream`.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+    let (mut uid, mut gid) = (0, 0);
+
+    // SAFETY: `uid` and `gid` outlive the call.
+    match unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } {
+        0 => Ok(uid),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Creates the directory of the socket with access for the current user only, and refuses one
+/// that someone else owns or can reach into, so no other user can bind or connect in its place.
+fn secure_directory(path: &Path) -> io::Result<()> {
+    let Some(directory) = path.parent() else {
+        return Ok(());
+    };
+
+    match DirBuilder::new().mode(0o700).create(directory) {
+        Err(error) if error.kind() == ErrorKind::AlreadyExists => {}
+        result => result?,
+    }
+
+    let metadata = fs::symlink_metadata(directory)?;
+
+    if !metadata.is_dir() || metadata.uid() != current_uid() || metadata.mode() & 0o077 != 0 {
+        return Err(io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!(
+                "{} has to be a directory only the current user can access",
+                directory.display()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+enum Instance {
+    First(UnixListener),
+    Running(UnixStream),
+}
+
+/// Listens on `path` unless another instance answers there, replacing a socket left behind by an
+/// instance that is gone. Only the current user may connect, the directory already keeps everyone
+/// else out while the socket still has the mode of the umask.
+fn claim(path: &Path) -> io::Result<Instance> {
+    if let Ok(stream) = UnixStream::connect(path) {
+        return Ok(Instance::Running(stream));
+    }
+
+    // Launches that find the same socket left behind take turns, otherwise one could remove the
+    // socket the other just bound. The lock is released once the socket is bound.
+    let lock = File::create(path.with_extension("lock"))?;
+    lock.lock()?;
+
+    // Another instance may have started listening while this one waited.
+    if let Ok(stream) = UnixStream::connect(path) {
+        return Ok(Instance::Running(stream));
+    }
+
+    match fs::remove_file(path) {
+        Err(error) if error.kind() == ErrorKind::NotFound => {}
+        result => result?,
+    }
+
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(Instance::First(listener))
+}
+
+/// Sends the launch flags to the running instance as commands and reports what it answered.
+fn forward(stream: UnixStream, launch: &LaunchFlags) -> ExitCode {
+    for (flag, given) in [
+        ("--dry-run", launch.dry_run),
+        ("--http", launch.http.is_some()),
+    ] {
+        if given {
+            eprintln!(
+                "Click-R is running already, {} only applies when it starts",
+                flag
+            );
+        }
+    }
+
+    let mut answers = BufReader::new(&stream);
+    let mut writer = &stream;
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for command in forwarded_commands(launch) {
+        let mut answer = String::new();
+        let sent = serde_json::to_string(&command)
+            .map_err(io::Error::from)
+            .and_then(|line| writeln!(writer, "{}", line))
+            .and_then(|()| answers.read_line(&mut answer));
+
+        let error = match sent {
+            Ok(0) => Some(String::from("the connection was closed")),
+            Ok(_) => match serde_json::from_str::<Value>(&answer) {
+                Ok(answer) if answer["ok"] == true => None,
+                Ok(answer) => Some(answer["error"].as_str().unwrap_or_default().to_string()),
+                Err(error) => Some(error.to_string()),
+            },
+            Err(error) => Some(error.to_string()),
+        };
+
+        if let Some(error) = error {
+            eprintln!("Click-R is running already: {}", error);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+/// Serves every connection of the current user on a thread of its own, others are closed unread.
+fn accept(listener: UnixListener, requests: futures_mpsc::UnboundedSender<ControlRequest>) {
+    for stream in listener.incoming().flatten() {
+        if peer_uid(&stream).ok() != Some(current_uid()) {
+            continue;
+        }
+
+        let requests = requests.clone();
+        thread::spawn(move || serve(stream, requests));
+    }
+}
+
+/// Reads commands from one connection until it closes, the answers and events are written by a
+/// thread of their own so events can arrive between commands.
+fn serve(stream: UnixStream, requests: futures_mpsc::UnboundedSender<ControlRequest>) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let (sender, lines) = mpsc::channel::<Value>();
+    let connection = Connection(sender);
+
+    thread::spawn(move || {
+        for line in lines {
+            if writeln!(writer, "{}", line).is_err() {
+                break;
+            }
+        }
+    });
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<ControlCommand>(&line) {
+            Ok(command) => {
+                let request = ControlRequest {
+                    command,
+                    connection: connection.clone(),
+                };
+
+                if requests.unbounded_send(request).is_err() {
+                    connection.error("Click-R is shutting down");
+                    break;
+                }
+            }
+            Err(error) => connection.error(format!("Invalid command: {}", error)),
+        }
+    }
+}
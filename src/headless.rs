@@ -0,0 +1,119 @@
+use crate::cli::RunArgs;
+use click_r_engine::fail_safe::{FailSafe, ScreenCorner};
+use click_r_engine::{ClickConfig, ClickJob, RunEvent, RunProgress, Runner, Schedule, StopReason};
+use std::process::ExitCode;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
+
+/// The conventional exit code of a process ended by SIGINT.
+const INTERRUPTED: u8 = 130;
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Runs a click job without a window and prints its progress to stderr.
+///
+/// Exits with `0` once a limit ends the run, `130` on Ctrl+C, and `1` if the mouse cannot be
+/// controlled, a fail-safe tripped or the click thread crashed.
+pub fn run(args: RunArgs) -> ExitCode {
+    let job = ClickJob {
+        clicks: ClickConfig {
+            interval: args.interval,
+            clicks_count: args.count,
+            button: args.button.into(),
+        },
+        duration: args.duration,
+        max_total_clicks: args.max_clicks.unwrap_or(0),
+        max_cycles: args.max_cycles.unwrap_or(0),
+        fail_safe: FailSafe {
+            corner: args
+                .fail_safe_corner
+                .map_or(ScreenCorner::None, ScreenCorner::from),
+            mouse_move_threshold: args.max_mouse_move.unwrap_or(0),
+            ..FailSafe::default()
+        },
+        ..ClickJob::default()
+    };
+    let schedule = Schedule {
+        start_after: args.delay,
+        stop_at: None,
+    };
+    let started_at = Instant::now();
+    let runner = Runner::start(job, schedule);
+    let events = runner.subscribe();
+    let handle = runner.handle();
+
+    if let Err(error) = ctrlc::set_handler(move || handle.stop(StopReason::User)) {
+        eprintln!(
+            "Unable to handle Ctrl+C, stop the run with a limit instead: {}",
+            error
+        );
+    }
+
+    let mut next_progress = started_at + PROGRESS_INTERVAL;
+
+    loop {
+        let timeout = next_progress.saturating_duration_since(Instant::now());
+
+        match events.recv_timeout(timeout) {
+            Ok(RunEvent::Finished(_)) | Err(RecvTimeoutError::Disconnected) => break,
+            Ok(_) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                if !args.quiet {
+                    print_progress(runner.progress(), started_at, args.delay);
+                }
+
+                next_progress += PROGRESS_INTERVAL;
+            }
+        }
+    }
+
+    let total_clicks = *runner.progress().total_clicks.lock().unwrap();
+    let reason = runner.join();
+
+    println!(
+        "{}: {} clicks in {}s",
+        reason,
+        total_clicks,
+        started_at.elapsed().as_secs()
+    );
+
+    match reason {
+        StopReason::User => ExitCode::from(INTERRUPTED),
+        StopReason::Duration
+        | StopReason::ScheduledStop
+        | StopReason::ClickLimit
+        | StopReason::CycleLimit
+        | StopReason::PatternComplete => ExitCode::SUCCESS,
+        StopReason::PanicKey
+        | StopReason::FailSafeCorner
+        | StopReason::FailSafeMouseMove
+        | StopReason::InputUnavailable
+        | StopReason::Crashed => ExitCode::FAILURE,
+    }
+}
+
+fn print_progress(progress: &RunProgress, started_at: Instant, delay: Duration) {
+    match *progress.clicking_started_at.lock().unwrap() {
+        Some(clicking_started_at) => {
+            let elapsed = clicking_started_at.elapsed().as_secs_f64();
+            let clicks = *progress.total_clicks.lock().unwrap();
+
+            eprintln!(
+                "Running {}s, {} clicks, {:.2} CPS",
+                elapsed as u64,
+                clicks,
+                if elapsed > 0.0 {
+                    clicks as f64 / elapsed
+                } else {
+                    0.0
+                }
+            );
+        }
+        None => eprintln!(
+            "Starting in {}s",
+            delay
+                .saturating_sub(started_at.elapsed())
+                .as_secs_f64()
+                .ceil()
+        ),
+    }
+}
@@ -1,94 +1,188 @@
+mod cli;
+mod click_config;
+mod commands;
+mod control;
+mod headless;
 mod message;
+mod panic_key;
+mod settings;
 mod theme;
+mod theme_import;
 mod update;
 mod utils;
 mod view;
 
+use crate::cli::{Cli, Command as CliCommand, LaunchFlags};
+use crate::control::{Connection, RunState};
 use crate::message::Message;
-use crate::theme::ThemeDef;
+use crate::panic_key::PanicKey;
+use crate::settings::{BundleError, ProfileBundle, SettingsError, SettingsFile};
+use crate::theme::{PaletteDraft, ThemeDef};
+use crate::theme_import::ThemeImportError;
 use crate::update::update_handler;
 use crate::utils::{deserialize_mouse_button, serialize_mouse_button};
 use crate::view::view_handler;
+use chrono::{DateTime, Local};
+use click_r_engine::fail_safe::ScreenCorner;
+use click_r_engine::pattern::{BurstPattern, PhaseStatus};
+use click_r_engine::schedule::{RecurringSchedule, ScheduleError};
+use click_r_engine::{RunEvent, Runner, StopReason};
 use enigo::Button as MouseButton;
-use iced::theme::Theme;
+use iced::theme::{Custom, Theme};
 use iced::{
-    event, executor, Application, Command, Element, Settings as IcedSettings, Subscription,
+    event, executor, window, Application, Command, Element, Settings as IcedSettings, Subscription,
 };
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::process::ExitCode;
 use std::sync::{mpsc, Arc, Mutex};
-use std::time::Duration;
-use std::{fs, thread};
+use std::time::{Duration, Instant};
 
-#[derive(Serialize, Deserialize)]
 struct AutoClicker {
+    active_profile: String,
+    /// The run state the control subscribers were last told about.
+    announced_state: RunState,
+    auto_save: bool,
+    bundle_path: String,
+    bundle_status: Option<Result<String, BundleError>>,
+    control_subscribers: Vec<Connection>,
+    clicking_started_at: Arc<Mutex<Option<Instant>>>,
+    custom_themes: Vec<Arc<Custom>>,
+    deferred_reload: Option<SettingsFile>,
+    delay_timer: u64,
+    dry_run: bool,
+    edited_settings: Value,
+    last_edit_at: Option<Instant>,
+    last_schedule_check: Option<DateTime<Local>>,
+    /// The last occurrence of each recurring run that came due while another run was going.
+    skipped_runs: Vec<(RecurringSchedule, DateTime<Local>)>,
+    time_running: u64,
+    is_paused: Arc<Mutex<bool>>,
+    is_running: Arc<Mutex<bool>>,
+    new_schedule_expression: String,
+    new_schedule_run_minutes: u64,
+    palette_draft: Option<PaletteDraft>,
+    pending_import: Option<ProfileBundle>,
+    phase_status: Arc<Mutex<Option<PhaseStatus>>>,
+    profile: Profile,
+    profile_name_input: String,
+    profiles: BTreeMap<String, Value>,
+    /// The active profile of the settings file while `--profile` opened another one for this
+    /// session only.
+    remembered_profile: Option<String>,
+    runner: Option<Runner>,
+    run_events: Option<mpsc::Receiver<RunEvent>>,
+    run_starts_at: Option<DateTime<Local>>,
+    save_on_close_failed: bool,
+    saved_settings: Value,
+    /// Why the last start was refused because of the start or stop time.
+    schedule_error: Option<ScheduleError>,
+    settings_conflict: Option<SettingsFile>,
+    settings_error: Option<SettingsError>,
+    stop_reason: Arc<Mutex<Option<StopReason>>>,
+    theme_import_error: Option<ThemeImportError>,
+    theme_import_path: String,
+    total_clicks: Arc<Mutex<u64>>,
+}
+
+/// The values saved in a profile, a profile switch replaces all of them and nothing else.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct Profile {
+    burst_pattern: BurstPattern,
     click_interval_slider_value: u8,
-    #[serde(skip)]
-    click_thread: Option<thread::JoinHandle<()>>,
     clicks_count_slider_value: u8,
+    cps_mode: bool,
     delay_hours: u64,
     delay_minutes: u64,
     delay_seconds: u64,
     duration_hours: u64,
     duration_minutes: u64,
     duration_seconds: u64,
-    #[serde(skip)]
-    delay_timer: u64,
-    #[serde(skip)]
-    time_running: u64,
-    #[serde(skip)]
-    is_running: Arc<Mutex<bool>>,
+    fail_safe_corner: ScreenCorner,
+    fail_safe_mouse_move_threshold: u32,
+    max_cycles: u64,
+    max_total_clicks: u64,
+    panic_key: PanicKey,
+    recurring_schedules: Vec<RecurringSchedule>,
+    scheduled_start: String,
+    scheduled_stop: String,
     #[serde(
         serialize_with = "serialize_mouse_button",
         deserialize_with = "deserialize_mouse_button"
     )]
     selected_mouse_button: Arc<Mutex<MouseButton>>,
-    #[serde(skip)]
-    stop_sender: Option<mpsc::Sender<()>>,
+    target_cps: String,
     #[serde(with = "ThemeDef")]
     theme: Theme,
-    #[serde(skip)]
-    ticks_count: u64,
-    #[serde(skip)]
-    total_clicks: Arc<Mutex<u32>>,
 }
 
 impl Application for AutoClicker {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        let default_settings = Self::default();
-
-        if Path::new("settings.json").exists() {
-            match fs::read_to_string("settings.json") {
-                Ok(settings) => match serde_json::from_str(&settings) {
-                    Ok(new_settings) => (new_settings, Command::none()),
-                    Err(_) => (default_settings, Command::none()),
-                },
-                Err(_) => (default_settings, Command::none()),
+    type Flags = LaunchFlags;
+
+    fn new(flags: Self::Flags) -> (Self, Command<Self::Message>) {
+        let (settings, mut settings_error) = match SettingsFile::load() {
+            Ok(settings) => (settings, None),
+            Err(error) => (SettingsFile::default(), Some(error)),
+        };
+
+        let (mut auto_clicker, profile_error) = settings.into_auto_clicker();
+        settings_error = settings_error.or(profile_error);
+
+        if let Some(profile) = flags.profile {
+            if profile != auto_clicker.active_profile
+                && auto_clicker.profiles.contains_key(&profile)
+            {
+                if let Err(error) = settings::switch_session_profile(&mut auto_clicker, &profile) {
+                    settings_error = settings_error.or(Some(error));
+                }
             }
-        } else {
-            (default_settings, Command::none())
         }
+
+        auto_clicker.settings_error = settings_error;
+        auto_clicker.dry_run = flags.dry_run;
+
+        let mut commands = Vec::new();
+
+        if flags.minimized {
+            commands.push(window::minimize(window::Id::MAIN, true));
+        }
+
+        if flags.start || flags.toggle {
+            commands.push(Command::perform(async {}, |()| Message::Start));
+        }
+
+        (auto_clicker, Command::batch(commands))
     }
 
     fn title(&self) -> String {
-        String::from("Click-R")
+        if self.dry_run {
+            String::from("Click-R (dry run)")
+        } else {
+            String::from("Click-R")
+        }
     }
 
     fn update(&mut self, message: Message) -> Command<Self::Message> {
         update_handler(self, message)
     }
 
-    fn view(&self) -> Element<Self::Message> {
+    fn view(&self) -> Element<'_, Self::Message> {
         view_handler(self)
     }
 
     fn theme(&self) -> Self::Theme {
-        self.theme.clone()
+        match &self.palette_draft {
+            Some(draft) => Theme::Custom(Arc::new(Custom::new(
+                draft.name.clone(),
+                draft.preview(self.profile.theme.palette()),
+            ))),
+            None => self.profile.theme.clone(),
+        }
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
@@ -102,37 +196,162 @@ impl Application for AutoClicker {
             iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
                 key, modifiers: _, ..
             }) => Message::KeyPressed(key),
+            iced::Event::Window(_, window::Event::CloseRequested) => Message::CloseRequested,
             _ => Message::None,
         });
 
-        Subscription::batch(vec![timer_subscription, keyboard_subscription])
+        let schedule_subscription = if self.profile.recurring_schedules.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(Duration::from_millis(1000)).map(|_| Message::CheckSchedules)
+        };
+
+        let auto_save_subscription = if self.auto_save && settings::has_unsaved_changes(self) {
+            iced::time::every(Duration::from_millis(500)).map(|_| Message::AutoSaveTick)
+        } else {
+            Subscription::none()
+        };
+
+        let deferred_reload_subscription = if self.deferred_reload.is_some() {
+            iced::time::every(Duration::from_millis(1000)).map(|_| Message::ApplyDeferredReload)
+        } else {
+            Subscription::none()
+        };
+
+        let run_events_subscription = if self.run_events.is_some() {
+            iced::time::every(Duration::from_millis(100)).map(|_| Message::PollRunEvents)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch(vec![
+            timer_subscription,
+            run_events_subscription,
+            schedule_subscription,
+            auto_save_subscription,
+            deferred_reload_subscription,
+            settings::watch::subscription(),
+            control::subscription(),
+            keyboard_subscription,
+        ])
     }
 }
 
 impl Default for AutoClicker {
     fn default() -> Self {
         Self {
+            active_profile: settings::DEFAULT_PROFILE.to_string(),
+            announced_state: RunState::Idle,
+            auto_save: false,
+            bundle_path: String::new(),
+            bundle_status: None,
+            control_subscribers: Vec::new(),
+            clicking_started_at: Arc::new(Mutex::new(None)),
+            custom_themes: Vec::new(),
+            deferred_reload: None,
+            delay_timer: 0,
+            dry_run: false,
+            edited_settings: Value::Null,
+            last_edit_at: None,
+            last_schedule_check: None,
+            skipped_runs: Vec::new(),
+            time_running: 0,
+            is_paused: Arc::new(Mutex::new(false)),
+            is_running: Arc::new(Mutex::new(false)),
+            new_schedule_expression: String::new(),
+            new_schedule_run_minutes: 0,
+            palette_draft: None,
+            pending_import: None,
+            phase_status: Arc::new(Mutex::new(None)),
+            profile: Profile::default(),
+            profile_name_input: String::new(),
+            profiles: BTreeMap::new(),
+            remembered_profile: None,
+            runner: None,
+            run_events: None,
+            run_starts_at: None,
+            save_on_close_failed: false,
+            saved_settings: Value::Null,
+            schedule_error: None,
+            settings_conflict: None,
+            settings_error: None,
+            stop_reason: Arc::new(Mutex::new(None)),
+            theme_import_error: None,
+            theme_import_path: String::new(),
+            total_clicks: Arc::new(Mutex::new(0)),
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            burst_pattern: BurstPattern::default(),
             click_interval_slider_value: 1,
-            click_thread: None,
             clicks_count_slider_value: 1,
+            cps_mode: false,
             delay_hours: 0,
             delay_minutes: 0,
             delay_seconds: 0,
-            delay_timer: 0,
             duration_hours: 0,
             duration_minutes: 0,
             duration_seconds: 0,
-            time_running: 0,
-            is_running: Arc::new(Mutex::new(false)),
+            fail_safe_corner: ScreenCorner::default(),
+            fail_safe_mouse_move_threshold: 0,
+            max_cycles: 0,
+            max_total_clicks: 0,
+            panic_key: PanicKey::default(),
+            recurring_schedules: Vec::new(),
+            scheduled_start: String::new(),
+            scheduled_stop: String::new(),
             selected_mouse_button: Arc::new(Mutex::new(MouseButton::Left)),
-            stop_sender: None,
+            target_cps: String::from("1"),
             theme: Theme::Oxocarbon,
-            ticks_count: 0,
-            total_clicks: Arc::new(Mutex::new(0)),
         }
     }
 }
 
-fn main() -> iced::Result {
-    AutoClicker::run(IcedSettings::default())
+fn main() -> ExitCode {
+    let cli = Cli::parse_args();
+    settings::init_path(cli.config, cli.portable);
+
+    match cli.command {
+        Some(CliCommand::Run(args)) => return headless::run(args),
+        Some(CliCommand::Profile(command)) => return commands::profile(command),
+        Some(CliCommand::Settings(command)) => return commands::settings(command),
+        None => {}
+    }
+
+    if let Err(error) = commands::check_launch_flags(&cli.launch) {
+        eprintln!("{}", error);
+        return ExitCode::FAILURE;
+    }
+
+    if let Some(exit_code) = control::claim_or_forward(&cli.launch) {
+        return exit_code;
+    }
+
+    if let Some(address) = cli.launch.http {
+        if let Err(error) = control::http::listen(address, cli.launch.http_token.clone()) {
+            eprintln!("Unable to serve remote control on {}: {}", address, error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let result = AutoClicker::run(IcedSettings {
+        flags: cli.launch,
+        window: window::Settings {
+            exit_on_close_request: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
 }
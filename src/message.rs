@@ -1,24 +1,99 @@
+use crate::control::ControlRequest;
+use crate::panic_key::PanicKey;
+use crate::settings::CollisionResolution;
+use crate::theme::PaletteColor;
+use click_r_engine::fail_safe::ScreenCorner;
+use click_r_engine::pattern::Phase;
 use enigo::Button;
 use iced::keyboard::Key;
 use iced::Theme;
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    AddPhase,
+    AddRecurringSchedule,
+    ApplyDeferredReload,
+    AutoSaveTick,
+    AutoSaveToggled(bool),
+    BundlePathChanged(String),
+    BurstPatternToggled(bool),
+    CancelImport,
+    CheckSchedules,
     ClickCountSliderChanged(u8),
+    CloseRequested,
+    Control(ControlRequest),
+    CpsModeToggled(bool),
+    CreateProfile,
+    CustomThemeNameChanged(String),
     DelayHoursChanged(u64),
     DelayMinutesChanged(u64),
     DelaySecondsChanged(u64),
+    DeleteProfile,
+    DismissSettingsError,
+    DuplicateProfile,
     DurationHoursChanged(u64),
     DurationMinutesChanged(u64),
     DurationSecondsChanged(u64),
+    ExportAllProfiles,
+    ExportProfile,
+    FailSafeCornerChanged(ScreenCorner),
+    FailSafeMouseMoveChanged(u32),
+    ImportProfiles,
+    ImportTheme,
     IntervalSliderChanged(u8),
+    KeepLocalSettings,
+    LoadSessionProfile(String),
+    MaxCyclesChanged(u64),
+    MaxTotalClicksChanged(u64),
+    NewScheduleExpressionChanged(String),
+    NewScheduleRunMinutesChanged(u64),
+    PaletteColorChanged(PaletteColor, String),
+    PaletteEditorToggled(bool),
+    PanicKeyChanged(PanicKey),
+    PatternRepeatChanged(u64),
+    Pause,
+    PhaseChanged(usize, Phase),
+    PollRunEvents,
+    ProfileNameInputChanged(String),
+    ProfileSelected(String),
+    RaiseWindow,
+    ReloadSettings,
+    RemovePhase(usize),
+    RemoveRecurringSchedule(usize),
+    RenameProfile,
     ResetToDefaults,
+    ResolveImport(CollisionResolution),
+    Resume,
+    SaveCustomTheme,
     SaveSettings,
+    ScheduledStartChanged(String),
+    ScheduledStopChanged(String),
     SelectMouseButton(Button),
+    SettingsFileChanged,
     Start,
     Stop,
+    TargetCpsChanged(String),
     ThemeChanged(Theme),
+    ThemeImportPathChanged(String),
     Tick,
+    Toggle,
     KeyPressed(Key),
-    None
+    None,
+}
+
+impl Message {
+    /// Whether handling the message can change what gets saved. Control requests that edit
+    /// anything do so through a message of their own.
+    pub fn can_edit_settings(&self) -> bool {
+        !matches!(
+            self,
+            Message::AutoSaveTick
+                | Message::CheckSchedules
+                | Message::Control(_)
+                | Message::None
+                | Message::PollRunEvents
+                | Message::RaiseWindow
+                | Message::Tick
+        )
+    }
 }
@@ -0,0 +1,17 @@
+use iced::keyboard::key::Named;
+
+pub use click_r_engine::panic_key::PanicKey;
+
+/// Whether `key` pressed in the window is the panic key. The runner watches for it everywhere
+/// else, this stops the run a little sooner while the window has the focus.
+pub fn matches(panic_key: PanicKey, key: Named) -> bool {
+    matches!(
+        (panic_key, key),
+        (PanicKey::Escape, Named::Escape)
+            | (PanicKey::Pause, Named::Pause)
+            | (PanicKey::F8, Named::F8)
+            | (PanicKey::F9, Named::F9)
+            | (PanicKey::F10, Named::F10)
+            | (PanicKey::F12, Named::F12)
+    )
+}
@@ -0,0 +1,465 @@
+use crate::settings::{migrations, BundleError, SETTINGS_VERSION};
+use crate::theme::{deserialize_custom_themes, is_built_in, serialize_custom_themes};
+use crate::Profile;
+use chrono::Local;
+use iced::theme::{Custom, Theme};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Tells a profile bundle apart from any other JSON file, including `settings.json`.
+pub const BUNDLE_FORMAT: &str = "click-r-profiles";
+/// Bump whenever the bundle or profile layout changes in a way older versions cannot read.
+pub const BUNDLE_VERSION: u64 = 1;
+/// The settings version of the profiles in bundles that do not record one.
+const FIRST_SETTINGS_VERSION: u64 = 3;
+
+/// One or more profiles exported to a single file to be shared.
+#[derive(Serialize, Deserialize)]
+pub struct ProfileBundle {
+    pub format: String,
+    pub version: u64,
+    pub exported_at: String,
+    /// The settings version the profiles and custom themes are stored with.
+    pub settings_version: u64,
+    pub profiles: BTreeMap<String, Value>,
+    /// The custom themes the profiles use, so they can be picked again after importing.
+    #[serde(
+        default,
+        serialize_with = "serialize_custom_themes",
+        deserialize_with = "deserialize_custom_themes"
+    )]
+    pub custom_themes: Vec<Arc<Custom>>,
+}
+
+/// What to do with imported profiles whose name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionResolution {
+    /// Import them as `Name (2)`, `Name (3)`, ...
+    Rename,
+    Overwrite,
+}
+
+impl ProfileBundle {
+    /// Bundles `profiles` together with those of `custom_themes` they use.
+    pub fn new(profiles: BTreeMap<String, Value>, custom_themes: &[Arc<Custom>]) -> Self {
+        let used: Vec<&str> = profiles
+            .values()
+            .filter_map(|profile| profile.pointer("/theme/Custom/name")?.as_str())
+            .collect();
+        let custom_themes = custom_themes
+            .iter()
+            .filter(|theme| used.contains(&theme.to_string().as_str()))
+            .cloned()
+            .collect();
+
+        Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: Local::now().to_rfc3339(),
+            settings_version: SETTINGS_VERSION,
+            profiles,
+            custom_themes,
+        }
+    }
+
+    /// Reads a bundle and checks that it is one, that this version understands it and that every
+    /// profile in it can be loaded. Profiles and custom themes stored with an older settings version
+    /// are migrated like a settings file.
+    pub fn load(path: &Path) -> Result<Self, BundleError> {
+        let invalid = |reason: String| BundleError::Invalid {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let contents = fs::read_to_string(path).map_err(|error| BundleError::Read {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        })?;
+        let mut document: Value =
+            serde_json::from_str(&contents).map_err(|error| invalid(error.to_string()))?;
+
+        if document.get("format").and_then(Value::as_str) != Some(BUNDLE_FORMAT) {
+            return Err(invalid(format!(
+                "the \"format\" field is not \"{}\"",
+                BUNDLE_FORMAT
+            )));
+        }
+
+        match document.get("version").and_then(Value::as_u64) {
+            Some(version) if version > BUNDLE_VERSION => {
+                return Err(BundleError::UnsupportedVersion {
+                    path: path.to_path_buf(),
+                    version,
+                })
+            }
+            Some(_) => {}
+            None => return Err(invalid("the \"version\" field is missing".to_string())),
+        }
+
+        let bundle_version = document["version"].take();
+        document["version"] = match document.get("settings_version") {
+            // Version 0 settings had no profiles to bundle.
+            Some(version) if !matches!(version.as_u64(), Some(1..)) => {
+                return Err(invalid(format!("unknown settings version {}", version)))
+            }
+            Some(version) => version.clone(),
+            None => json!(FIRST_SETTINGS_VERSION),
+        };
+        let mut document = migrations::migrate(document).map_err(invalid)?;
+        document["version"] = bundle_version;
+        document["settings_version"] = json!(SETTINGS_VERSION);
+
+        let bundle: Self =
+            serde_json::from_value(document).map_err(|error| invalid(error.to_string()))?;
+
+        if bundle.profiles.is_empty() {
+            return Err(invalid("it contains no profiles".to_string()));
+        }
+
+        for (name, profile) in &bundle.profiles {
+            if name.trim().is_empty() {
+                return Err(invalid("a profile has an empty name".to_string()));
+            }
+
+            serde_json::from_value::<Profile>(profile.clone())
+                .map_err(|error| invalid(format!("profile \"{}\": {}", name, error)))?;
+        }
+
+        Ok(bundle)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BundleError> {
+        let write_error = |reason: String| BundleError::Write {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let contents =
+            serde_json::to_string_pretty(self).map_err(|error| write_error(error.to_string()))?;
+
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|error| write_error(error.to_string()))?;
+        }
+
+        fs::write(path, contents).map_err(|error| write_error(error.to_string()))
+    }
+
+    /// The names in the bundle that are already used by `profiles`.
+    pub fn collisions(&self, profiles: &BTreeMap<String, Value>) -> Vec<String> {
+        self.profiles
+            .keys()
+            .filter(|name| profiles.contains_key(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Adds the profiles to `profiles` and their custom themes to `custom_themes`, and returns the
+    /// names the profiles were stored under.
+    ///
+    /// A custom theme whose name is taken by a built-in theme or by a custom theme with other
+    /// colors is imported as `Name (imported)`, and the profiles using it follow the new name.
+    pub fn merge_into(
+        mut self,
+        profiles: &mut BTreeMap<String, Value>,
+        custom_themes: &mut Vec<Arc<Custom>>,
+        resolution: CollisionResolution,
+    ) -> Vec<String> {
+        for theme in self.custom_themes {
+            let name = theme.to_string();
+            let palette = Theme::Custom(Arc::clone(&theme)).palette();
+            let free_name = theme_names(&name)
+                .find(|candidate| {
+                    !is_built_in(candidate)
+                        && custom_themes.iter().all(|existing| {
+                            existing.to_string() != *candidate
+                                || Theme::Custom(Arc::clone(existing)).palette() == palette
+                        })
+                })
+                .unwrap();
+
+            if !custom_themes
+                .iter()
+                .any(|existing| existing.to_string() == free_name)
+            {
+                custom_themes.push(Arc::new(Custom::new(free_name.clone(), palette)));
+            }
+
+            if free_name != name {
+                for profile in self.profiles.values_mut() {
+                    if let Some(used) = profile.pointer_mut("/theme/Custom/name") {
+                        if *used == name {
+                            *used = Value::from(free_name.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut imported = Vec::new();
+
+        for (name, profile) in self.profiles {
+            let name = match resolution {
+                CollisionResolution::Overwrite => name,
+                CollisionResolution::Rename => free_name(profiles, &name),
+            };
+
+            profiles.insert(name.clone(), profile);
+            imported.push(name);
+        }
+
+        imported
+    }
+}
+
+/// `name`, then `name (imported)`, `name (imported 2)`, ...
+fn theme_names(name: &str) -> impl Iterator<Item = String> + '_ {
+    std::iter::once(name.to_string())
+        .chain(std::iter::once(format!("{} (imported)", name)))
+        .chain((2..).map(move |suffix| format!("{} (imported {})", name, suffix)))
+}
+
+fn free_name(profiles: &BTreeMap<String, Value>, name: &str) -> String {
+    if !profiles.contains_key(name) {
+        return name.to_string();
+    }
+
+    (2..)
+        .map(|suffix| format!("{} ({})", name, suffix))
+        .find(|candidate| !profiles.contains_key(candidate))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::snapshot;
+    use crate::theme::to_hex;
+    use iced::theme::Palette;
+    use iced::Color;
+    use std::path::PathBuf;
+    use std::process;
+
+    struct BundleFile(PathBuf);
+
+    impl BundleFile {
+        fn new(name: &str, contents: &Value) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "click-r-bundle-test-{}-{}.json",
+                process::id(),
+                name
+            ));
+            fs::write(&path, contents.to_string()).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for BundleFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn custom(name: &str, background: Color) -> Arc<Custom> {
+        Arc::new(Custom::new(
+            name.to_string(),
+            Palette {
+                background,
+                ..Palette::DARK
+            },
+        ))
+    }
+
+    fn profile_with_theme(theme: &Arc<Custom>) -> Value {
+        let mut profile = snapshot(&Profile::default());
+        profile["theme"] = json!({ "Custom": serde_json::to_value(Stored(theme)).unwrap() });
+        profile
+    }
+
+    #[derive(Serialize)]
+    struct Stored<'a>(
+        #[serde(serialize_with = "crate::theme::serialize_arc_custom_theme")] &'a Arc<Custom>,
+    );
+
+    fn bundle(profiles: &[(&str, Value)], custom_themes: &[Arc<Custom>]) -> ProfileBundle {
+        let profiles = profiles
+            .iter()
+            .map(|(name, profile)| (name.to_string(), profile.clone()))
+            .collect();
+        ProfileBundle::new(profiles, custom_themes)
+    }
+
+    fn theme_names(custom_themes: &[Arc<Custom>]) -> Vec<String> {
+        custom_themes.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn imports_profiles_and_the_themes_they_use() {
+        let forest = custom("Forest", Color::from_rgb8(0x10, 0x30, 0x10));
+        let unused = custom("Unused", Color::BLACK);
+        let bundle = bundle(
+            &[("Farming", profile_with_theme(&forest))],
+            &[Arc::clone(&forest), unused],
+        );
+        let mut profiles = BTreeMap::new();
+        let mut custom_themes = Vec::new();
+
+        let imported = bundle.merge_into(
+            &mut profiles,
+            &mut custom_themes,
+            CollisionResolution::Rename,
+        );
+
+        assert_eq!(imported, ["Farming"]);
+        assert_eq!(theme_names(&custom_themes), ["Forest"]);
+        assert_eq!(profiles["Farming"], profile_with_theme(&forest));
+    }
+
+    #[test]
+    fn renames_or_overwrites_profiles_with_a_taken_name() {
+        let taken = BTreeMap::from([
+            ("Farming".to_string(), json!({ "max_cycles": 1 })),
+            ("Farming (2)".to_string(), json!({ "max_cycles": 2 })),
+        ]);
+        let incoming = [("Farming", json!({ "max_cycles": 3 }))];
+
+        let mut profiles = taken.clone();
+        let imported = bundle(&incoming, &[]).merge_into(
+            &mut profiles,
+            &mut Vec::new(),
+            CollisionResolution::Rename,
+        );
+        assert_eq!(imported, ["Farming (3)"]);
+        assert_eq!(profiles.len(), 3);
+
+        let mut profiles = taken;
+        let imported = bundle(&incoming, &[]).merge_into(
+            &mut profiles,
+            &mut Vec::new(),
+            CollisionResolution::Overwrite,
+        );
+        assert_eq!(imported, ["Farming"]);
+        assert_eq!(profiles["Farming"], json!({ "max_cycles": 3 }));
+    }
+
+    #[test]
+    fn reuses_a_custom_theme_with_the_same_colors() {
+        let forest = custom("Forest", Color::from_rgb8(0x10, 0x30, 0x10));
+        let mut custom_themes = vec![Arc::clone(&forest)];
+        let mut profiles = BTreeMap::new();
+
+        bundle(&[("Farming", profile_with_theme(&forest))], &[forest]).merge_into(
+            &mut profiles,
+            &mut custom_themes,
+            CollisionResolution::Rename,
+        );
+
+        assert_eq!(theme_names(&custom_themes), ["Forest"]);
+        assert_eq!(profiles["Farming"]["theme"]["Custom"]["name"], "Forest");
+    }
+
+    #[test]
+    fn renames_a_custom_theme_whose_name_is_taken() {
+        let local = custom("Forest", Color::from_rgb8(0x10, 0x30, 0x10));
+        let incoming = custom("Forest", Color::from_rgb8(0x30, 0x10, 0x10));
+        let built_in = custom("Dark", Color::from_rgb8(0x20, 0x20, 0x20));
+        let mut custom_themes = vec![local];
+        let mut profiles = BTreeMap::new();
+
+        bundle(
+            &[
+                ("Farming", profile_with_theme(&incoming)),
+                ("Night", profile_with_theme(&built_in)),
+            ],
+            &[Arc::clone(&incoming), built_in],
+        )
+        .merge_into(
+            &mut profiles,
+            &mut custom_themes,
+            CollisionResolution::Rename,
+        );
+
+        assert_eq!(
+            theme_names(&custom_themes),
+            ["Forest", "Forest (imported)", "Dark (imported)"]
+        );
+        assert_eq!(
+            Theme::Custom(Arc::clone(&custom_themes[1]))
+                .palette()
+                .background,
+            Color::from_rgb8(0x30, 0x10, 0x10)
+        );
+        assert_eq!(
+            profiles["Farming"]["theme"]["Custom"]["name"],
+            "Forest (imported)"
+        );
+        assert_eq!(
+            profiles["Night"]["theme"]["Custom"]["name"],
+            "Dark (imported)"
+        );
+    }
+
+    #[test]
+    fn loads_a_saved_bundle() {
+        let forest = custom("Forest", Color::from_rgb8(0x10, 0x30, 0x10));
+        let saved = bundle(&[("Farming", profile_with_theme(&forest))], &[forest]);
+        let file = BundleFile::new("saved", &json!({}));
+        saved.save(&file.0).unwrap();
+
+        let loaded = ProfileBundle::load(&file.0).unwrap();
+
+        assert_eq!(loaded.settings_version, SETTINGS_VERSION);
+        assert_eq!(loaded.profiles, saved.profiles);
+        assert_eq!(theme_names(&loaded.custom_themes), ["Forest"]);
+    }
+
+    #[test]
+    fn migrates_profiles_of_an_older_settings_version() {
+        let file = BundleFile::new(
+            "older",
+            &json!({
+                "format": BUNDLE_FORMAT,
+                "version": 1,
+                "exported_at": "2026-10-19T09:00:00+00:00",
+                "settings_version": 2,
+                "profiles": { "Farming": { "theme": { "Custom": "Forest" } } },
+                "custom_themes": ["Forest"],
+            }),
+        );
+
+        let loaded = ProfileBundle::load(&file.0).unwrap();
+
+        assert_eq!(
+            loaded.profiles["Farming"]["theme"]["Custom"]["background"],
+            to_hex(Palette::DARK.background)
+        );
+        assert_eq!(theme_names(&loaded.custom_themes), ["Forest"]);
+    }
+
+    #[test]
+    fn rejects_profiles_of_a_newer_or_unknown_settings_version() {
+        for settings_version in [json!(SETTINGS_VERSION + 1), json!(0), json!("3")] {
+            let file = BundleFile::new(
+                "newer",
+                &json!({
+                    "format": BUNDLE_FORMAT,
+                    "version": 1,
+                    "exported_at": "2026-10-19T09:00:00+00:00",
+                    "settings_version": settings_version,
+                    "profiles": { "Farming": {} },
+                }),
+            );
+
+            assert!(matches!(
+                ProfileBundle::load(&file.0),
+                Err(BundleError::Invalid { .. })
+            ));
+        }
+    }
+}
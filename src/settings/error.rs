@@ -0,0 +1,116 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Why the settings file could not be loaded or saved, shown in the status area of the window.
+#[derive(Debug, Clone)]
+pub enum SettingsError {
+    Read {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The file or one of its profiles was read but is not usable, the file has been backed up to
+    /// `backup` if that succeeded.
+    Invalid {
+        path: PathBuf,
+        reason: String,
+        backup: Option<PathBuf>,
+    },
+    Write {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl SettingsError {
+    /// Records where the settings file was backed up to.
+    pub fn backed_up(self, backup: Option<PathBuf>) -> Self {
+        match self {
+            SettingsError::Invalid { path, reason, .. } => SettingsError::Invalid {
+                path,
+                reason,
+                backup,
+            },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Read { path, reason } => {
+                write!(f, "Unable to read {}: {}", path.display(), reason)
+            }
+            SettingsError::Invalid {
+                path,
+                reason,
+                backup,
+            } => {
+                write!(f, "Unable to load {}: {}", path.display(), reason)?;
+
+                match backup {
+                    Some(backup) => write!(f, " (backed up to {})", backup.display()),
+                    None => Ok(()),
+                }
+            }
+            SettingsError::Write { path, reason } => {
+                write!(f, "Unable to save {}: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// Why a profile bundle could not be exported or imported.
+#[derive(Debug, Clone)]
+pub enum BundleError {
+    Read {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The file is not a profile bundle or one of its profiles cannot be loaded.
+    Invalid {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The bundle was written by a newer version of Click-R.
+    UnsupportedVersion {
+        path: PathBuf,
+        version: u64,
+    },
+    Write {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Read { path, reason } => {
+                write!(f, "Unable to read {}: {}", path.display(), reason)
+            }
+            BundleError::Invalid { path, reason } => {
+                write!(
+                    f,
+                    "{} is not a usable profile bundle: {}",
+                    path.display(),
+                    reason
+                )
+            }
+            BundleError::UnsupportedVersion { path, version } => write!(
+                f,
+                "{} is a version {} profile bundle, this version of Click-R reads up to version {}",
+                path.display(),
+                version,
+                super::bundle::BUNDLE_VERSION
+            ),
+            BundleError::Write { path, reason } => {
+                write!(f, "Unable to export to {}: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
@@ -0,0 +1,212 @@
+use crate::settings::{DEFAULT_PROFILE, SETTINGS_VERSION};
+use crate::theme::to_hex;
+use iced::theme::Palette;
+use serde_json::{json, Value};
+
+/// Brings a settings document of any known layout up to [`SETTINGS_VERSION`].
+///
+/// Fails for documents that are not settings or come from a newer version of Click-R.
+pub fn migrate(mut value: Value) -> Result<Value, String> {
+    if !value.is_object() {
+        return Err("not a settings document".to_string());
+    }
+
+    let mut version = detect_version(&value)?;
+
+    if version > SETTINGS_VERSION {
+        return Err(format!(
+            "written by a newer Click-R (settings version {}, this one supports up to {})",
+            version, SETTINGS_VERSION
+        ));
+    }
+
+    while version < SETTINGS_VERSION {
+        value = match version {
+            0 => v0_to_v1(value),
+            1 => v1_to_v2(value),
+            2 => v2_to_v3(value),
+            _ => unreachable!("no migration from settings version {}", version),
+        };
+        version += 1;
+    }
+
+    Ok(value)
+}
+
+/// Files written before the `version` field existed are told apart by their layout.
+fn detect_version(value: &Value) -> Result<u64, String> {
+    match value.get("version") {
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("unknown settings version {}", version)),
+        None if value.get("profiles").is_some() => Ok(1),
+        None => Ok(0),
+    }
+}
+
+/// Version 0 was the flat settings of a single setup, it becomes the default profile.
+fn v0_to_v1(value: Value) -> Value {
+    json!({
+        "active_profile": DEFAULT_PROFILE,
+        "profiles": { DEFAULT_PROFILE: value },
+    })
+}
+
+/// Version 2 records the version in the file.
+fn v1_to_v2(mut value: Value) -> Value {
+    value["version"] = json!(2);
+    value
+}
+
+/// Version 3 adds `auto_save` and the shared `custom_themes`, and stores every custom theme with
+/// its palette as hex colors where version 2 only kept its name.
+fn v2_to_v3(mut value: Value) -> Value {
+    if let Some(themes) = value.get_mut("custom_themes").and_then(Value::as_array_mut) {
+        themes.iter_mut().for_each(add_palette);
+    }
+
+    if let Some(profiles) = value.get_mut("profiles").and_then(Value::as_object_mut) {
+        for profile in profiles.values_mut() {
+            if let Some(theme) = profile.pointer_mut("/theme/Custom") {
+                add_palette(theme);
+            }
+        }
+    }
+
+    value["version"] = json!(3);
+    value
+}
+
+/// Gives a custom theme stored by name only the dark palette it was shown with.
+fn add_palette(theme: &mut Value) {
+    if let Some(name) = theme.as_str() {
+        let palette = Palette::DARK;
+
+        *theme = json!({
+            "name": name,
+            "background": to_hex(palette.background),
+            "text": to_hex(palette.text),
+            "primary": to_hex(palette.primary),
+            "success": to_hex(palette.success),
+            "danger": to_hex(palette.danger),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{profile_values, SettingsFile};
+    use enigo::Button as MouseButton;
+    use iced::Theme;
+    use std::path::Path;
+
+    fn fixture(contents: &str) -> Value {
+        serde_json::from_str(contents).unwrap()
+    }
+
+    fn dark_palette(name: &str) -> Value {
+        let palette = Palette::DARK;
+
+        json!({
+            "name": name,
+            "background": to_hex(palette.background),
+            "text": to_hex(palette.text),
+            "primary": to_hex(palette.primary),
+            "success": to_hex(palette.success),
+            "danger": to_hex(palette.danger),
+        })
+    }
+
+    #[test]
+    fn migrates_version_0_into_the_default_profile() {
+        let original = fixture(include_str!("fixtures/v0.json"));
+        let migrated = migrate(original.clone()).unwrap();
+
+        assert_eq!(migrated["version"], json!(SETTINGS_VERSION));
+        assert_eq!(migrated["active_profile"], json!(DEFAULT_PROFILE));
+        assert_eq!(migrated["profiles"][DEFAULT_PROFILE], original);
+
+        let settings = SettingsFile::parse(&migrated.to_string()).unwrap();
+        let profile = profile_values(&settings.profiles, DEFAULT_PROFILE).unwrap();
+        assert_eq!(profile.click_interval_slider_value, 7);
+        assert_eq!(profile.delay_seconds, 30);
+        assert_eq!(profile.duration_minutes, 5);
+        assert_eq!(
+            *profile.selected_mouse_button.lock().unwrap(),
+            MouseButton::Right
+        );
+        assert_eq!(profile.theme, Theme::Dracula);
+    }
+
+    #[test]
+    fn migrates_version_1_and_gives_custom_themes_a_palette() {
+        let migrated = migrate(fixture(include_str!("fixtures/v1.json"))).unwrap();
+
+        assert_eq!(migrated["version"], json!(SETTINGS_VERSION));
+        assert_eq!(migrated["active_profile"], json!("Farming"));
+        assert_eq!(
+            migrated["profiles"]["Farming"]["theme"],
+            json!({ "Custom": dark_palette("Forest") })
+        );
+        assert_eq!(migrated["profiles"]["Default"]["theme"], json!("Light"));
+
+        let settings = SettingsFile::parse(&migrated.to_string()).unwrap();
+        assert!(!settings.auto_save);
+        assert!(settings.custom_themes.is_empty());
+        assert!(settings.profile_errors(Path::new("v1.json")).is_empty());
+    }
+
+    #[test]
+    fn migrates_version_2_and_gives_custom_themes_a_palette() {
+        let migrated = migrate(fixture(include_str!("fixtures/v2.json"))).unwrap();
+
+        assert_eq!(migrated["version"], json!(SETTINGS_VERSION));
+        assert_eq!(migrated["custom_themes"], json!([dark_palette("Forest")]));
+        assert_eq!(
+            migrated["profiles"]["Default"]["theme"],
+            json!({ "Custom": dark_palette("Forest") })
+        );
+
+        let settings = SettingsFile::parse(&migrated.to_string()).unwrap();
+        assert_eq!(settings.custom_themes.len(), 1);
+        assert_eq!(settings.custom_themes[0].to_string(), "Forest");
+    }
+
+    #[test]
+    fn keeps_the_current_version_as_it_is() {
+        let current = migrate(fixture(include_str!("fixtures/v2.json"))).unwrap();
+
+        assert_eq!(migrate(current.clone()), Ok(current));
+    }
+
+    #[test]
+    fn keeps_unknown_keys() {
+        let migrated = migrate(fixture(include_str!("fixtures/v2.json"))).unwrap();
+
+        assert_eq!(migrated["window"], json!({ "maximized": true }));
+        assert_eq!(
+            migrated["profiles"]["Default"]["window_position"],
+            json!([40, 80])
+        );
+    }
+
+    #[test]
+    fn rejects_newer_and_unknown_versions() {
+        let newer = migrate(json!({ "version": SETTINGS_VERSION + 1, "profiles": {} }));
+        assert!(newer.unwrap_err().contains("newer Click-R"));
+
+        for version in [json!("3"), json!(-1), json!(2.5), json!(null)] {
+            let unknown = migrate(json!({ "version": version, "profiles": {} }));
+            assert!(
+                unknown.unwrap_err().starts_with("unknown settings version"),
+                "{version}"
+            );
+        }
+
+        assert_eq!(
+            migrate(json!(["Default"])),
+            Err("not a settings document".to_string())
+        );
+    }
+}
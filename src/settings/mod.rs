@@ -0,0 +1,484 @@
+mod bundle;
+mod error;
+mod migrations;
+pub mod watch;
+
+pub use bundle::{CollisionResolution, ProfileBundle};
+pub use error::{BundleError, SettingsError};
+
+use crate::theme::{deserialize_custom_themes, serialize_custom_themes};
+use crate::{AutoClicker, Profile};
+use chrono::Local;
+use iced::theme::Custom;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const DEFAULT_PROFILE: &str = "Default";
+/// Bump together with a new step in [`migrations::migrate`] whenever the layout changes.
+pub const SETTINGS_VERSION: u64 = 3;
+
+/// Next to the executable, turns on portable mode without passing `--portable`.
+const PORTABLE_MARKER: &str = "portable";
+
+static SETTINGS_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Picks the settings file for this process, must run before any settings are loaded.
+///
+/// An explicit `config` wins, portable mode keeps settings next to the executable, and otherwise
+/// they live in the user config directory (`$XDG_CONFIG_HOME/click-r/` on Linux).
+pub fn init_path(config: Option<PathBuf>, portable: bool) {
+    let path = match config {
+        Some(config) => config,
+        None if portable || executable_dir().join(PORTABLE_MARKER).exists() => {
+            executable_dir().join(SETTINGS_FILE)
+        }
+        None => {
+            let path = user_config_path();
+            migrate_working_directory_settings(&path);
+            path
+        }
+    };
+
+    let _ = SETTINGS_PATH.set(path);
+}
+
+/// The settings file in use.
+pub fn path() -> &'static Path {
+    SETTINGS_PATH.get_or_init(user_config_path)
+}
+
+fn user_config_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|config_dir| config_dir.join("click-r").join(SETTINGS_FILE))
+        .unwrap_or_else(|| PathBuf::from(SETTINGS_FILE))
+}
+
+/// Moves an unusable settings file aside as `settings.json.<timestamp>.bak`.
+fn back_up_unreadable() -> Option<PathBuf> {
+    let backup = backup_path()?;
+    fs::rename(path(), &backup).ok().map(|_| backup)
+}
+
+/// Copies a settings file with an unusable profile to `settings.json.<timestamp>.bak`, the file
+/// itself stays in use.
+fn back_up_copy() -> Option<PathBuf> {
+    let backup = backup_path()?;
+    fs::copy(path(), &backup).ok().map(|_| backup)
+}
+
+fn backup_path() -> Option<PathBuf> {
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+
+    (0..)
+        .map(|attempt| {
+            let mut backup = path().as_os_str().to_owned();
+
+            match attempt {
+                0 => backup.push(format!(".{}.bak", timestamp)),
+                _ => backup.push(format!(".{}-{}.bak", timestamp, attempt)),
+            }
+
+            PathBuf::from(backup)
+        })
+        .find(|backup| !backup.exists())
+}
+
+fn executable_dir() -> PathBuf {
+    env::current_exe()
+        .ok()
+        .and_then(|executable| executable.parent().map(Path::to_path_buf))
+        .unwrap_or_default()
+}
+
+/// Moves a `settings.json` left in the working directory by older versions into `path`, unless
+/// settings already exist there.
+fn migrate_working_directory_settings(path: &Path) {
+    let legacy = Path::new(SETTINGS_FILE);
+
+    if path.exists() || !legacy.is_file() {
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+
+    if fs::copy(legacy, path).is_ok() {
+        let _ = fs::remove_file(legacy);
+    }
+}
+
+/// The layout of `settings.json`: every named profile plus the one that was active last, and the
+/// custom themes any profile can pick.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsFile {
+    pub version: u64,
+    pub active_profile: String,
+    pub auto_save: bool,
+    #[serde(
+        serialize_with = "serialize_custom_themes",
+        deserialize_with = "deserialize_custom_themes"
+    )]
+    pub custom_themes: Vec<Arc<Custom>>,
+    pub profiles: BTreeMap<String, Value>,
+}
+
+impl Default for SettingsFile {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            active_profile: DEFAULT_PROFILE.to_string(),
+            auto_save: false,
+            custom_themes: Vec::new(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl SettingsFile {
+    /// Reads the settings file and migrates it from older layouts.
+    ///
+    /// A file that can be read but not used is backed up next to itself, so falling back to
+    /// defaults and saving afterwards never destroys a setup that could still be recovered by hand.
+    pub fn load() -> Result<Self, SettingsError> {
+        if !path().exists() {
+            return Ok(Self::default());
+        }
+
+        Self::read(path(), back_up_unreadable)
+    }
+
+    /// Reads the settings file after another program changed it. An unusable file is left alone,
+    /// since it may still be in the middle of being written.
+    pub fn reload() -> Result<Self, SettingsError> {
+        Self::read(path(), || None)
+    }
+
+    /// Reads any settings file and checks that every profile in it loads, without changing it.
+    pub fn validate(file: &Path) -> Result<Self, SettingsError> {
+        let settings = Self::inspect(file)?;
+
+        match settings.profile_errors(file).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(settings),
+        }
+    }
+
+    /// Reads any settings file without changing it, profiles that do not load are kept as they are.
+    pub fn inspect(file: &Path) -> Result<Self, SettingsError> {
+        Self::read(file, || None)
+    }
+
+    /// Explains why each profile that cannot be loaded is unusable, `file` is the one it came from.
+    pub fn profile_errors(&self, file: &Path) -> Vec<SettingsError> {
+        self.profiles
+            .iter()
+            .filter_map(|(name, profile)| {
+                let error = serde_json::from_value::<Profile>(profile.clone()).err()?;
+
+                Some(SettingsError::Invalid {
+                    path: file.to_path_buf(),
+                    reason: format!("profile \"{}\": {}", name, error),
+                    backup: None,
+                })
+            })
+            .collect()
+    }
+
+    fn read(file: &Path, back_up: fn() -> Option<PathBuf>) -> Result<Self, SettingsError> {
+        let settings = fs::read_to_string(file).map_err(|error| SettingsError::Read {
+            path: file.to_path_buf(),
+            reason: error.to_string(),
+        })?;
+
+        Self::parse(&settings).map_err(|reason| SettingsError::Invalid {
+            path: file.to_path_buf(),
+            reason,
+            backup: back_up(),
+        })
+    }
+
+    fn parse(settings: &str) -> Result<Self, String> {
+        let value = serde_json::from_str(settings).map_err(|error| error.to_string())?;
+        let value = migrations::migrate(value)?;
+        serde_json::from_value(value).map_err(|error| error.to_string())
+    }
+
+    /// Collects every profile of the app, with the active one taken from its current values.
+    pub fn from_auto_clicker(auto_clicker: &AutoClicker) -> Self {
+        let mut profiles = auto_clicker.profiles.clone();
+        profiles.insert(
+            auto_clicker.active_profile.clone(),
+            snapshot(&auto_clicker.profile),
+        );
+
+        Self {
+            version: SETTINGS_VERSION,
+            active_profile: auto_clicker
+                .remembered_profile
+                .clone()
+                .unwrap_or_else(|| auto_clicker.active_profile.clone()),
+            auto_save: auto_clicker.auto_save,
+            custom_themes: auto_clicker.custom_themes.clone(),
+            profiles,
+        }
+    }
+
+    /// Writes the settings to a temporary file first and renames it over the old one, so an
+    /// interrupted save leaves the previous settings intact.
+    pub fn save(&self) -> Result<(), SettingsError> {
+        let write_error = |error: io::Error| SettingsError::Write {
+            path: path().to_path_buf(),
+            reason: error.to_string(),
+        };
+
+        let settings = serde_json::to_string(self)
+            .map_err(io::Error::from)
+            .map_err(write_error)?;
+
+        if let Some(parent) = path().parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+
+        let mut temporary = path().as_os_str().to_owned();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let written = File::create(&temporary).and_then(|mut file| {
+            file.write_all(settings.as_bytes())?;
+            file.sync_all()
+        });
+
+        if let Err(error) = written.and_then(|_| fs::rename(&temporary, path())) {
+            let _ = fs::remove_file(&temporary);
+            return Err(write_error(error));
+        }
+
+        Ok(())
+    }
+
+    /// Builds the app state for the active profile, falling back to defaults if it is missing.
+    ///
+    /// An active profile that cannot be loaded is kept as it is and another one becomes active
+    /// instead, the settings file is backed up and the error names the profile.
+    pub fn into_auto_clicker(self) -> (AutoClicker, Option<SettingsError>) {
+        self.into_auto_clicker_with(back_up_copy)
+    }
+
+    fn into_auto_clicker_with(
+        mut self,
+        back_up: fn() -> Option<PathBuf>,
+    ) -> (AutoClicker, Option<SettingsError>) {
+        self.ensure_profiles();
+
+        let (profile, error) = match profile_values(&self.profiles, &self.active_profile) {
+            Ok(profile) => (profile, None),
+            Err(error) => {
+                let (name, profile) = self.fallback_profile();
+                self.active_profile = name;
+                (profile, Some(error.backed_up(back_up())))
+            }
+        };
+
+        let mut auto_clicker = AutoClicker {
+            profile,
+            ..AutoClicker::default()
+        };
+        auto_clicker.profiles = self.profiles;
+        auto_clicker.active_profile = self.active_profile;
+        auto_clicker.auto_save = self.auto_save;
+        auto_clicker.custom_themes = self.custom_themes;
+        auto_clicker.saved_settings = SettingsFile::from_auto_clicker(&auto_clicker).to_value();
+        auto_clicker.edited_settings = auto_clicker.saved_settings.clone();
+        (auto_clicker, error)
+    }
+
+    /// The first profile that loads, Default preferred, or a new one with default values when
+    /// none does.
+    fn fallback_profile(&mut self) -> (String, Profile) {
+        let names =
+            std::iter::once(DEFAULT_PROFILE).chain(self.profiles.keys().map(String::as_str));
+
+        for name in names {
+            if let Ok(profile) = profile_values(&self.profiles, name) {
+                return (name.to_string(), profile);
+            }
+        }
+
+        let name = (1..)
+            .map(|number| format!("{} {}", DEFAULT_PROFILE, number))
+            .find(|name| !self.profiles.contains_key(name))
+            .unwrap();
+        self.profiles
+            .insert(name.clone(), snapshot(&Profile::default()));
+        (name, Profile::default())
+    }
+
+    /// Adds the active and the Default profile with default values if either is missing.
+    pub fn ensure_profiles(&mut self) {
+        for name in [self.active_profile.clone(), DEFAULT_PROFILE.to_string()] {
+            self.profiles
+                .entry(name)
+                .or_insert_with(|| snapshot(&Profile::default()));
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// The serialized values of a profile.
+pub fn snapshot(profile: &Profile) -> Value {
+    serde_json::to_value(profile).unwrap()
+}
+
+/// Replaces the profile values of `auto_clicker`, the list of profiles, the run in progress and
+/// the rest of the session stay.
+pub fn replace_profile_values(auto_clicker: &mut AutoClicker, values: Profile) {
+    // The last schedule check went stale while the profile had no schedules to check.
+    if auto_clicker.profile.recurring_schedules.is_empty() {
+        auto_clicker.last_schedule_check = None;
+    }
+
+    auto_clicker.profile = values;
+}
+
+/// Saves every profile and remembers the result as the saved state.
+pub fn save(auto_clicker: &mut AutoClicker) -> Result<(), SettingsError> {
+    let settings = SettingsFile::from_auto_clicker(auto_clicker);
+    settings.save()?;
+    auto_clicker.saved_settings = settings.to_value();
+    auto_clicker.edited_settings = auto_clicker.saved_settings.clone();
+    auto_clicker.last_edit_at = None;
+    Ok(())
+}
+
+/// Compares the settings with the saved ones after every message that can edit them and notes
+/// when they last changed, which is what the auto-save debounce waits on.
+pub fn track_changes(auto_clicker: &mut AutoClicker) {
+    let edited_settings = SettingsFile::from_auto_clicker(auto_clicker).to_value();
+
+    if edited_settings != auto_clicker.edited_settings {
+        auto_clicker.edited_settings = edited_settings;
+        auto_clicker.last_edit_at = Some(Instant::now());
+    }
+}
+
+pub fn has_unsaved_changes(auto_clicker: &AutoClicker) -> bool {
+    auto_clicker.edited_settings != auto_clicker.saved_settings
+}
+
+/// The values of the profile called `name`, the defaults if there is none.
+pub fn profile_values(
+    profiles: &BTreeMap<String, Value>,
+    name: &str,
+) -> Result<Profile, SettingsError> {
+    match profiles.get(name) {
+        Some(profile) => {
+            serde_json::from_value(profile.clone()).map_err(|error| SettingsError::Invalid {
+                path: path().to_path_buf(),
+                reason: format!("profile \"{}\": {}", name, error),
+                backup: None,
+            })
+        }
+        None => Ok(Profile::default()),
+    }
+}
+
+/// Stores the current values of the active profile and loads the profile called `name`.
+pub fn switch_profile(auto_clicker: &mut AutoClicker, name: &str) -> Result<(), SettingsError> {
+    let values = profile_values(&auto_clicker.profiles, name)?;
+    auto_clicker.profiles.insert(
+        auto_clicker.active_profile.clone(),
+        snapshot(&auto_clicker.profile),
+    );
+    replace_profile_values(auto_clicker, values);
+    auto_clicker.active_profile = name.to_string();
+    Ok(())
+}
+
+/// Switches to the profile called `name` for this session only, the settings file keeps the
+/// profile it had as the active one.
+pub fn switch_session_profile(
+    auto_clicker: &mut AutoClicker,
+    name: &str,
+) -> Result<(), SettingsError> {
+    let remembered = auto_clicker
+        .remembered_profile
+        .take()
+        .unwrap_or_else(|| auto_clicker.active_profile.clone());
+    let switched = switch_profile(auto_clicker, name);
+    auto_clicker.remembered_profile =
+        (auto_clicker.active_profile != remembered).then_some(remembered);
+    switched
+}
+
+/// Loads the profile called `name` without storing the values of the active one. A profile that
+/// cannot be loaded leaves the active one in place.
+pub fn load_profile(auto_clicker: &mut AutoClicker, name: &str) -> Result<(), SettingsError> {
+    let values = profile_values(&auto_clicker.profiles, name)?;
+    replace_profile_values(auto_clicker, values);
+    auto_clicker.active_profile = name.to_string();
+    Ok(())
+}
+
+/// Replaces every profile and global setting with the ones from `settings`, which then count as
+/// saved.
+pub fn apply_external(auto_clicker: &mut AutoClicker, settings: SettingsFile) {
+    let (reloaded, error) = settings.into_auto_clicker_with(|| None);
+    auto_clicker.auto_save = reloaded.auto_save;
+    auto_clicker.custom_themes = reloaded.custom_themes;
+    auto_clicker.profiles = reloaded.profiles;
+    replace_profile_values(auto_clicker, reloaded.profile);
+    auto_clicker.active_profile = reloaded.active_profile;
+    auto_clicker.remembered_profile = None;
+    auto_clicker.saved_settings = reloaded.saved_settings;
+    auto_clicker.edited_settings = auto_clicker.saved_settings.clone();
+    auto_clicker.last_edit_at = None;
+    auto_clicker.settings_error = error;
+}
+
+/// Records the active profile in the settings file without saving any other pending edits, also
+/// when `--profile` opened it for this session only. A profile the file does not have yet, such as
+/// a newly created one, is added with its current values.
+///
+/// A settings file that cannot be read is left alone and reported instead of being replaced.
+pub fn remember_active_profile(auto_clicker: &mut AutoClicker) -> Result<(), SettingsError> {
+    auto_clicker.remembered_profile = None;
+    let mut settings = if path().exists() {
+        SettingsFile::reload()?
+    } else {
+        SettingsFile::default()
+    };
+
+    let name = auto_clicker.active_profile.clone();
+    let added = !settings.profiles.contains_key(&name);
+
+    if added {
+        settings
+            .profiles
+            .insert(name.clone(), snapshot(&auto_clicker.profile));
+    }
+
+    settings.active_profile = name.clone();
+    settings.save()?;
+    auto_clicker.saved_settings["active_profile"] = Value::from(name.clone());
+
+    if added {
+        auto_clicker.saved_settings["profiles"][name] = snapshot(&auto_clicker.profile);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::message::Message;
+use crate::settings::path;
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+/// Reports every change to the settings file made by another program (inotify on Linux).
+///
+/// The directory is watched instead of the file itself, because editors and scripts often replace
+/// the file by renaming a new one over it, which would end a watch on the old file.
+pub fn subscription() -> Subscription<Message> {
+    struct SettingsWatcher;
+
+    subscription::channel(
+        std::any::TypeId::of::<SettingsWatcher>(),
+        10,
+        |mut output| async move {
+            let (sender, mut events) = mpsc::unbounded();
+            let file_name = path().file_name().map(ToOwned::to_owned);
+
+            let watcher = RecommendedWatcher::new(
+                move |event: notify::Result<notify::Event>| {
+                    if let Ok(event) = event {
+                        let _ = sender.unbounded_send(event);
+                    }
+                },
+                notify::Config::default(),
+            )
+            .and_then(|mut watcher| {
+                let directory = match path().parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => parent,
+                    _ => Path::new("."),
+                };
+
+                // On a first run the directory only appears with the first save, create it now so
+                // there is something to watch.
+                fs::create_dir_all(directory)
+                    .map_err(notify::Error::io)
+                    .and_then(|_| watcher.watch(directory, RecursiveMode::NonRecursive))
+                    .map(|_| watcher)
+            });
+
+            // Without a watcher there is nothing to report, the settings still load and save.
+            let _watcher = watcher
+                .map_err(|error| eprintln!("Unable to watch the settings file: {}", error))
+                .ok();
+
+            loop {
+                let Some(event) = events.next().await else {
+                    iced::futures::future::pending::<()>().await;
+                    continue;
+                };
+
+                if touches_settings(&event, file_name.as_deref()) {
+                    let _ = output.send(Message::SettingsFileChanged).await;
+                }
+            }
+        },
+    )
+}
+
+/// Whether `event` changed the file called `file_name`, merely reading it does not count.
+fn touches_settings(event: &notify::Event, file_name: Option<&OsStr>) -> bool {
+    !event.kind.is_access()
+        && event
+            .paths
+            .iter()
+            .any(|changed| changed.file_name() == file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, EventKind, ModifyKind, RenameMode};
+
+    fn event(kind: EventKind, paths: &[&str]) -> notify::Event {
+        paths.iter().fold(notify::Event::new(kind), |event, path| {
+            event.add_path(path.into())
+        })
+    }
+
+    #[test]
+    fn reports_writes_and_replacements_of_the_settings_file() {
+        let settings = Some(OsStr::new("settings.json"));
+
+        for kind in [
+            EventKind::Modify(ModifyKind::Any),
+            EventKind::Create(CreateKind::File),
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+        ] {
+            let written = event(
+                kind,
+                &["/config/settings.json.tmp", "/config/settings.json"],
+            );
+            assert!(touches_settings(&written, settings), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn ignores_reads_and_other_files() {
+        let settings = Some(OsStr::new("settings.json"));
+
+        let read = event(
+            EventKind::Access(AccessKind::Any),
+            &["/config/settings.json"],
+        );
+        assert!(!touches_settings(&read, settings));
+
+        let other = event(
+            EventKind::Modify(ModifyKind::Any),
+            &["/config/settings.json.tmp"],
+        );
+        assert!(!touches_settings(&other, settings));
+    }
+}
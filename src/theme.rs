@@ -1,6 +1,8 @@
 use iced::theme::{Custom, Palette};
-use iced::Theme;
+use iced::{Color, Theme};
+use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 use std::sync::Arc;
 
 #[derive(Serialize, Deserialize)]
@@ -34,25 +36,361 @@ pub enum ThemeDef {
     Custom(Arc<Custom>),
 }
 
-pub fn serialize_arc_custom_theme<S>(_: &Arc<Custom>, serializer: S) -> Result<S::Ok, S::Error>
+/// A custom theme as stored in the settings file, every color is a `#rrggbb` or `#rrggbbaa` string.
+#[derive(Serialize, Deserialize)]
+struct CustomThemeDef {
+    name: String,
+    background: String,
+    text: String,
+    primary: String,
+    success: String,
+    danger: String,
+}
+
+/// Older settings files stored every custom theme as a bare name without its colors.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredCustomTheme {
+    Palette(CustomThemeDef),
+    NameOnly(String),
+}
+
+pub fn serialize_arc_custom_theme<S>(custom: &Arc<Custom>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    "Rust".serialize(serializer)
+    let palette = Theme::Custom(Arc::clone(custom)).palette();
+
+    CustomThemeDef {
+        name: custom.to_string(),
+        background: to_hex(palette.background),
+        text: to_hex(palette.text),
+        primary: to_hex(palette.primary),
+        success: to_hex(palette.success),
+        danger: to_hex(palette.danger),
+    }
+    .serialize(serializer)
 }
 
-pub fn deserialize_arc_custom_theme<'de, D>(_: D) -> Result<Arc<Custom>, D::Error>
+pub fn deserialize_arc_custom_theme<'de, D>(deserializer: D) -> Result<Arc<Custom>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    Ok(Arc::new(Custom::new(
-        "Rust".to_string(),
-        Palette {
-            background: Default::default(),
-            text: Default::default(),
-            primary: Default::default(),
-            success: Default::default(),
-            danger: Default::default(),
-        },
-    )))
+    let theme = match StoredCustomTheme::deserialize(deserializer)? {
+        StoredCustomTheme::Palette(theme) => theme,
+        StoredCustomTheme::NameOnly(name) => return Ok(Arc::new(Custom::new(name, Palette::DARK))),
+    };
+
+    let color = |field: &str, value: &str| {
+        parse_hex(value).ok_or_else(|| {
+            D::Error::custom(format!(
+                "invalid {} color \"{}\" in theme \"{}\"",
+                field, value, theme.name
+            ))
+        })
+    };
+
+    let palette = Palette {
+        background: color("background", &theme.background)?,
+        text: color("text", &theme.text)?,
+        primary: color("primary", &theme.primary)?,
+        success: color("success", &theme.success)?,
+        danger: color("danger", &theme.danger)?,
+    };
+
+    Ok(Arc::new(Custom::new(theme.name, palette)))
+}
+
+/// Whether `name` is taken by one of the themes that come with iced.
+pub fn is_built_in(name: &str) -> bool {
+    Theme::ALL.iter().any(|theme| theme.to_string() == name)
+}
+
+/// Formats a color as `#rrggbb`, or `#rrggbbaa` when it is not opaque.
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+
+    if a == u8::MAX {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa`, the leading `#` is optional.
+pub fn parse_hex(input: &str) -> Option<Color> {
+    let digits = input.trim().trim_start_matches('#');
+
+    if !matches!(digits.len(), 6 | 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).ok();
+    let alpha = if digits.len() == 8 {
+        channel(6)?
+    } else {
+        u8::MAX
+    };
+
+    Some(Color::from_rgba8(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+        alpha as f32 / 255.0,
+    ))
+}
+
+/// The custom themes saved next to the profiles, each one stored like [`ThemeDef::Custom`].
+pub fn serialize_custom_themes<S>(themes: &[Arc<Custom>], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    #[derive(Serialize)]
+    struct Stored<'a>(#[serde(serialize_with = "serialize_arc_custom_theme")] &'a Arc<Custom>);
+
+    serializer.collect_seq(themes.iter().map(Stored))
+}
+
+pub fn deserialize_custom_themes<'de, D>(deserializer: D) -> Result<Vec<Arc<Custom>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Stored(#[serde(deserialize_with = "deserialize_arc_custom_theme")] Arc<Custom>);
+
+    let themes = Vec::<Stored>::deserialize(deserializer)?;
+    Ok(themes.into_iter().map(|Stored(theme)| theme).collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteColor {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Danger,
+}
+
+impl PaletteColor {
+    pub const ALL: [PaletteColor; 5] = [
+        PaletteColor::Background,
+        PaletteColor::Text,
+        PaletteColor::Primary,
+        PaletteColor::Success,
+        PaletteColor::Danger,
+    ];
+
+    pub fn get(&self, palette: &Palette) -> Color {
+        match self {
+            PaletteColor::Background => palette.background,
+            PaletteColor::Text => palette.text,
+            PaletteColor::Primary => palette.primary,
+            PaletteColor::Success => palette.success,
+            PaletteColor::Danger => palette.danger,
+        }
+    }
+
+    pub fn set(&self, palette: &mut Palette, color: Color) {
+        match self {
+            PaletteColor::Background => palette.background = color,
+            PaletteColor::Text => palette.text = color,
+            PaletteColor::Primary => palette.primary = color,
+            PaletteColor::Success => palette.success = color,
+            PaletteColor::Danger => palette.danger = color,
+        }
+    }
+}
+
+impl fmt::Display for PaletteColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// What the palette editor holds while open, the colors are kept as typed so a half-entered hex
+/// value is not lost.
+#[derive(Debug, Clone)]
+pub struct PaletteDraft {
+    pub name: String,
+    pub colors: [String; 5],
+}
+
+impl PaletteDraft {
+    /// Starts from the colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let palette = theme.palette();
+
+        Self {
+            name: String::new(),
+            colors: PaletteColor::ALL.map(|color| to_hex(color.get(&palette))),
+        }
+    }
+
+    pub fn color(&self, color: PaletteColor) -> &str {
+        &self.colors[color as usize]
+    }
+
+    /// The palette to preview, colors that do not parse yet keep their value from `fallback`.
+    pub fn preview(&self, mut fallback: Palette) -> Palette {
+        for color in PaletteColor::ALL {
+            if let Some(value) = parse_hex(self.color(color)) {
+                color.set(&mut fallback, value);
+            }
+        }
+
+        fallback
+    }
+
+    /// Builds the theme to save, or says what is still missing. The name must not be taken by a
+    /// built-in theme or one of `custom_themes`, saving never replaces a theme.
+    pub fn to_custom(&self, custom_themes: &[Arc<Custom>]) -> Result<Custom, String> {
+        let name = self.name.trim();
+
+        if name.is_empty() {
+            return Err("Enter a name for the theme".to_string());
+        }
+
+        if is_built_in(name) {
+            return Err(format!("\"{}\" is a built-in theme", name));
+        }
+
+        if custom_themes
+            .iter()
+            .any(|custom| custom.to_string() == name)
+        {
+            return Err(format!("A custom theme called \"{}\" already exists", name));
+        }
+
+        let mut palette = Theme::default().palette();
+
+        for color in PaletteColor::ALL {
+            let value = parse_hex(self.color(color)).ok_or_else(|| {
+                format!(
+                    "{} color \"{}\" is not #rrggbb or #rrggbbaa",
+                    color,
+                    self.color(color)
+                )
+            })?;
+            color.set(&mut palette, value);
+        }
+
+        Ok(Custom::new(name.to_string(), palette))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize)]
+    struct Stored {
+        #[serde(with = "ThemeDef")]
+        theme: Theme,
+        #[serde(
+            serialize_with = "serialize_custom_themes",
+            deserialize_with = "deserialize_custom_themes"
+        )]
+        custom_themes: Vec<Arc<Custom>>,
+    }
+
+    fn forest() -> Arc<Custom> {
+        Arc::new(Custom::new(
+            "Forest".to_string(),
+            Palette {
+                background: Color::from_rgb8(0x10, 0x20, 0x30),
+                text: Color::from_rgb8(0xf0, 0xe0, 0xd0),
+                primary: Color::from_rgba8(0x22, 0x88, 0x44, 0x80 as f32 / 255.0),
+                success: Color::from_rgb8(0x00, 0xff, 0x00),
+                danger: Color::from_rgb8(0xff, 0x00, 0x00),
+            },
+        ))
+    }
+
+    #[test]
+    fn stores_every_color_of_a_custom_palette() {
+        let stored = Stored {
+            theme: Theme::Custom(forest()),
+            custom_themes: vec![forest()],
+        };
+        let expected = json!({
+            "name": "Forest",
+            "background": "#102030",
+            "text": "#f0e0d0",
+            "primary": "#22884480",
+            "success": "#00ff00",
+            "danger": "#ff0000",
+        });
+
+        assert_eq!(
+            serde_json::to_value(&stored).unwrap(),
+            json!({ "theme": { "Custom": expected }, "custom_themes": [expected] })
+        );
+    }
+
+    #[test]
+    fn reads_back_every_color_of_a_custom_palette() {
+        let stored = Stored {
+            theme: Theme::Custom(forest()),
+            custom_themes: vec![forest()],
+        };
+        let read: Stored = serde_json::from_value(serde_json::to_value(&stored).unwrap()).unwrap();
+        let expected = Theme::Custom(forest()).palette();
+        let read_themes = [
+            read.theme,
+            Theme::Custom(Arc::clone(&read.custom_themes[0])),
+        ];
+
+        for theme in read_themes {
+            assert_eq!(theme.to_string(), "Forest");
+
+            let palette = theme.palette();
+            assert_eq!(palette.background, expected.background);
+            assert_eq!(palette.text, expected.text);
+            assert_eq!(palette.primary, expected.primary);
+            assert_eq!(palette.success, expected.success);
+            assert_eq!(palette.danger, expected.danger);
+        }
+    }
+
+    #[test]
+    fn rejects_an_invalid_color() {
+        let error = serde_json::from_value::<Stored>(json!({
+            "theme": "Dark",
+            "custom_themes": [{
+                "name": "Forest",
+                "background": "#102030",
+                "text": "green",
+                "primary": "#228844",
+                "success": "#00ff00",
+                "danger": "#ff0000",
+            }],
+        }))
+        .err()
+        .unwrap();
+
+        assert!(error
+            .to_string()
+            .contains("invalid text color \"green\" in theme \"Forest\""));
+    }
+
+    #[test]
+    fn does_not_save_over_a_theme_with_the_same_name() {
+        let mut draft = PaletteDraft::from_theme(&Theme::Dark);
+
+        draft.name = "Dark".to_string();
+        assert_eq!(
+            draft.to_custom(&[]).err().unwrap(),
+            "\"Dark\" is a built-in theme"
+        );
+
+        draft.name = " Forest ".to_string();
+        assert_eq!(
+            draft.to_custom(&[forest()]).err().unwrap(),
+            "A custom theme called \"Forest\" already exists"
+        );
+
+        draft.name = "Meadow".to_string();
+        assert_eq!(draft.to_custom(&[forest()]).unwrap().to_string(), "Meadow");
+    }
 }
@@ -0,0 +1,483 @@
+use crate::theme::{is_built_in, parse_hex};
+use iced::theme::{Custom, Palette};
+use iced::Color;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The terminal color scheme formats a theme can be imported from, picked by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemeFormat {
+    /// `*.toml`, the `[colors.primary]` and `[colors.normal]` tables.
+    Alacritty,
+    /// `*.yaml` / `*.yml`, the `base00` to `base0F` keys.
+    Base16,
+    /// `*.itermcolors`, a plist of `Ansi N Color` and `Background Color` dictionaries.
+    ITerm,
+    /// `*.conf`, `background`, `foreground` and `colorN` lines.
+    Kitty,
+}
+
+impl SchemeFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "toml" => Some(SchemeFormat::Alacritty),
+            "yaml" | "yml" => Some(SchemeFormat::Base16),
+            "itermcolors" => Some(SchemeFormat::ITerm),
+            "conf" => Some(SchemeFormat::Kitty),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SchemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SchemeFormat::Alacritty => "Alacritty",
+            SchemeFormat::Base16 => "base16",
+            SchemeFormat::ITerm => "iTerm",
+            SchemeFormat::Kitty => "Kitty",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ThemeImportError {
+    Read {
+        path: PathBuf,
+        reason: String,
+    },
+    UnknownFormat(PathBuf),
+    Parse {
+        format: SchemeFormat,
+        reason: String,
+    },
+    MissingKey {
+        format: SchemeFormat,
+        key: String,
+    },
+    InvalidColor {
+        format: SchemeFormat,
+        key: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for ThemeImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeImportError::Read { path, reason } => {
+                write!(f, "Could not read {}: {}", path.display(), reason)
+            }
+            ThemeImportError::UnknownFormat(path) => write!(
+                f,
+                "Unknown color scheme format for {}, expected .toml (Alacritty), .yaml (base16), \
+                 .itermcolors (iTerm) or .conf (Kitty)",
+                path.display()
+            ),
+            ThemeImportError::Parse { format, reason } => {
+                write!(f, "Not a valid {} color scheme: {}", format, reason)
+            }
+            ThemeImportError::MissingKey { format, key } => {
+                write!(f, "The {} color scheme has no \"{}\" color", format, key)
+            }
+            ThemeImportError::InvalidColor { format, key, value } => write!(
+                f,
+                "The {} color scheme has an invalid \"{}\" color: \"{}\"",
+                format, key, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ThemeImportError {}
+
+/// Reads a terminal color scheme and maps it onto a palette: the background and foreground become
+/// the background and text, blue becomes primary, green success and red danger.
+///
+/// The theme is named after the scheme if the file names it, and after the file otherwise. A name
+/// taken by a built-in theme gets ` (imported)` appended.
+pub fn import(path: &Path) -> Result<Custom, ThemeImportError> {
+    let format = SchemeFormat::from_path(path)
+        .ok_or_else(|| ThemeImportError::UnknownFormat(path.into()))?;
+    let contents = fs::read_to_string(path).map_err(|error| ThemeImportError::Read {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    })?;
+
+    let (name, palette) = match format {
+        SchemeFormat::Alacritty => parse_alacritty(&contents)?,
+        SchemeFormat::Base16 => parse_base16(&contents)?,
+        SchemeFormat::ITerm => parse_iterm(&contents)?,
+        SchemeFormat::Kitty => parse_kitty(&contents)?,
+    };
+
+    let name = name
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| Some(path.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| format.to_string());
+
+    Ok(Custom::new(theme_name(&name), palette))
+}
+
+fn theme_name(name: &str) -> String {
+    let name = name.trim();
+
+    if is_built_in(name) {
+        format!("{} (imported)", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Looks up the five palette colors by the keys a format uses for them.
+fn palette(
+    format: SchemeFormat,
+    [background, text, primary, success, danger]: [&str; 5],
+    mut color: impl FnMut(&str) -> Option<String>,
+) -> Result<Palette, ThemeImportError> {
+    let mut get = |key: &str| {
+        let value = color(key).ok_or_else(|| ThemeImportError::MissingKey {
+            format,
+            key: key.to_string(),
+        })?;
+
+        parse_hex(&value.replacen("0x", "", 1)).ok_or(ThemeImportError::InvalidColor {
+            format,
+            key: key.to_string(),
+            value,
+        })
+    };
+
+    Ok(Palette {
+        background: get(background)?,
+        text: get(text)?,
+        primary: get(primary)?,
+        success: get(success)?,
+        danger: get(danger)?,
+    })
+}
+
+fn parse_alacritty(contents: &str) -> Result<(Option<String>, Palette), ThemeImportError> {
+    let format = SchemeFormat::Alacritty;
+    let document: toml::Table =
+        contents
+            .parse()
+            .map_err(|error: toml::de::Error| ThemeImportError::Parse {
+                format,
+                reason: error.message().to_string(),
+            })?;
+
+    let palette = palette(
+        format,
+        [
+            "colors.primary.background",
+            "colors.primary.foreground",
+            "colors.normal.blue",
+            "colors.normal.green",
+            "colors.normal.red",
+        ],
+        |key| {
+            let mut value = document.get("colors")?;
+
+            for part in key.split('.').skip(1) {
+                value = value.get(part)?;
+            }
+
+            value.as_str().map(str::to_string)
+        },
+    )?;
+
+    Ok((None, palette))
+}
+
+fn parse_base16(contents: &str) -> Result<(Option<String>, Palette), ThemeImportError> {
+    let format = SchemeFormat::Base16;
+    let document: HashMap<String, serde_yaml::Value> =
+        serde_yaml::from_str(contents).map_err(|error| ThemeImportError::Parse {
+            format,
+            reason: error.to_string(),
+        })?;
+
+    let value = |key: &str| yaml_string(document.get(key)?);
+
+    // base16 files list the colors either at the top level or under a `palette` key.
+    let palette = palette(
+        format,
+        ["base00", "base05", "base0D", "base0B", "base08"],
+        |key| value(key).or_else(|| yaml_string(document.get("palette")?.get(key)?)),
+    )?;
+
+    Ok((value("scheme").or_else(|| value("name")), palette))
+}
+
+/// Unquoted colors such as `000000` or `001100` are read by YAML as numbers without their leading
+/// zeros, they are padded back to six digits.
+fn yaml_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(value) => Some(value.clone()),
+        serde_yaml::Value::Number(number) => Some(match number.as_u64() {
+            Some(digits) if digits <= 999_999 => format!("{:06}", digits),
+            _ => number.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+fn parse_iterm(contents: &str) -> Result<(Option<String>, Palette), ThemeImportError> {
+    let format = SchemeFormat::ITerm;
+    let document = plist::Value::from_reader_xml(contents.as_bytes()).map_err(|error| {
+        ThemeImportError::Parse {
+            format,
+            reason: error.to_string(),
+        }
+    })?;
+    let document = document
+        .as_dictionary()
+        .ok_or_else(|| ThemeImportError::Parse {
+            format,
+            reason: "the top level is not a dictionary".to_string(),
+        })?;
+
+    let palette = palette(
+        format,
+        [
+            "Background Color",
+            "Foreground Color",
+            "Ansi 4 Color",
+            "Ansi 2 Color",
+            "Ansi 1 Color",
+        ],
+        |key| {
+            let color = document.get(key)?.as_dictionary()?;
+            let component = |name: &str| {
+                color
+                    .get(&format!("{} Component", name))
+                    .and_then(|value| value.as_real())
+                    .map(|value| value as f32)
+            };
+            let alpha = component("Alpha").unwrap_or(1.0);
+
+            Some(crate::theme::to_hex(Color::from_rgba(
+                component("Red")?,
+                component("Green")?,
+                component("Blue")?,
+                alpha,
+            )))
+        },
+    )?;
+
+    Ok((None, palette))
+}
+
+fn parse_kitty(contents: &str) -> Result<(Option<String>, Palette), ThemeImportError> {
+    let mut name = None;
+    let mut colors = HashMap::new();
+
+    for line in contents.lines().map(str::trim) {
+        // Kitty theme files name themselves in a `## name: ...` metadata comment.
+        if let Some(value) = line.strip_prefix("## name:") {
+            name = Some(value.trim().to_string());
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(char::is_whitespace) {
+            colors.insert(key.to_string(), value.trim().to_string());
+        }
+    }
+
+    let palette = palette(
+        SchemeFormat::Kitty,
+        ["background", "foreground", "color4", "color2", "color1"],
+        |key| colors.get(key).cloned(),
+    )?;
+
+    Ok((name, palette))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::to_hex;
+
+    fn hex(palette: &Palette) -> [String; 5] {
+        [
+            palette.background,
+            palette.text,
+            palette.primary,
+            palette.success,
+            palette.danger,
+        ]
+        .map(to_hex)
+    }
+
+    const EXPECTED: [&str; 5] = ["#1d1f21", "#c5c8c6", "#81a2be", "#b5bd68", "#cc6666"];
+
+    #[test]
+    fn alacritty() {
+        let (name, palette) = parse_alacritty(
+            r##"
+[colors.primary]
+background = "#1d1f21"
+foreground = "#c5c8c6"
+
+[colors.normal]
+red = "#cc6666"
+green = "0xb5bd68"
+blue = "#81a2be"
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(name, None);
+        assert_eq!(hex(&palette), EXPECTED);
+    }
+
+    #[test]
+    fn alacritty_missing_color() {
+        let error = parse_alacritty("[colors.primary]\nbackground = \"#1d1f21\"\n").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ThemeImportError::MissingKey { key, .. } if key == "colors.primary.foreground"
+        ));
+    }
+
+    #[test]
+    fn base16() {
+        let (name, palette) = parse_base16(
+            r#"
+scheme: "Tomorrow Night"
+base00: "1d1f21"
+base05: "c5c8c6"
+base08: "cc6666"
+base0B: "b5bd68"
+base0D: "81a2be"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(name.as_deref(), Some("Tomorrow Night"));
+        assert_eq!(hex(&palette), EXPECTED);
+    }
+
+    #[test]
+    fn base16_palette_key() {
+        let (name, palette) = parse_base16(
+            r##"
+name: "Tomorrow Night"
+palette:
+  base00: "#1d1f21"
+  base05: "#c5c8c6"
+  base08: "#cc6666"
+  base0B: "#b5bd68"
+  base0D: "#81a2be"
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(name.as_deref(), Some("Tomorrow Night"));
+        assert_eq!(hex(&palette), EXPECTED);
+    }
+
+    #[test]
+    fn base16_unquoted_numbers_keep_leading_zeros() {
+        let (_, palette) = parse_base16(
+            r#"
+base00: 000000
+base05: 001100
+base08: 123456
+base0B: "b5bd68"
+base0D: "81a2be"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(to_hex(palette.background), "#000000");
+        assert_eq!(to_hex(palette.text), "#001100");
+        assert_eq!(to_hex(palette.danger), "#123456");
+    }
+
+    #[test]
+    fn base16_invalid_color() {
+        let error = parse_base16(
+            "base00: 1234567\nbase05: c5c8c6\nbase08: cc6666\nbase0B: b5bd68\nbase0D: 81a2be\n",
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ThemeImportError::InvalidColor { key, value, .. } if key == "base00" && value == "1234567"
+        ));
+    }
+
+    #[test]
+    fn iterm() {
+        let color = |name: &str, [red, green, blue]: [u8; 3]| {
+            format!(
+                "<key>{}</key><dict>\
+                 <key>Red Component</key><real>{}</real>\
+                 <key>Green Component</key><real>{}</real>\
+                 <key>Blue Component</key><real>{}</real>\
+                 </dict>",
+                name,
+                red as f32 / 255.0,
+                green as f32 / 255.0,
+                blue as f32 / 255.0
+            )
+        };
+        let contents = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0"><dict>{}{}{}{}{}</dict></plist>"#,
+            color("Background Color", [0x1d, 0x1f, 0x21]),
+            color("Foreground Color", [0xc5, 0xc8, 0xc6]),
+            color("Ansi 4 Color", [0x81, 0xa2, 0xbe]),
+            color("Ansi 2 Color", [0xb5, 0xbd, 0x68]),
+            color("Ansi 1 Color", [0xcc, 0x66, 0x66]),
+        );
+
+        let (name, palette) = parse_iterm(&contents).unwrap();
+
+        assert_eq!(name, None);
+        assert_eq!(hex(&palette), EXPECTED);
+    }
+
+    #[test]
+    fn iterm_not_a_plist() {
+        assert!(matches!(
+            parse_iterm("background #1d1f21").unwrap_err(),
+            ThemeImportError::Parse { .. }
+        ));
+    }
+
+    #[test]
+    fn kitty() {
+        let (name, palette) = parse_kitty(
+            "## name: Tomorrow Night\n\
+             # A comment\n\
+             background #1d1f21\n\
+             foreground   #c5c8c6\n\
+             color1 #cc6666\n\
+             color2 #b5bd68\n\
+             color4 #81a2be\n",
+        )
+        .unwrap();
+
+        assert_eq!(name.as_deref(), Some("Tomorrow Night"));
+        assert_eq!(hex(&palette), EXPECTED);
+    }
+
+    #[test]
+    fn built_in_names_are_renamed() {
+        assert_eq!(theme_name(" Dracula "), "Dracula (imported)");
+        assert_eq!(theme_name("Tomorrow Night"), "Tomorrow Night");
+    }
+}
@@ -0,0 +1,13 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use click_r_engine::pattern::Phase;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    auto_clicker
+        .profile
+        .burst_pattern
+        .phases
+        .push(Phase::default());
+    Command::none()
+}
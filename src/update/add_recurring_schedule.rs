@@ -0,0 +1,25 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use click_r_engine::schedule::{parse_cron, RecurringSchedule};
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if parse_cron(&auto_clicker.new_schedule_expression).is_ok() {
+        // The last check went stale while there was nothing to check, so start counting afresh.
+        if auto_clicker.profile.recurring_schedules.is_empty() {
+            auto_clicker.last_schedule_check = None;
+        }
+
+        auto_clicker
+            .profile
+            .recurring_schedules
+            .push(RecurringSchedule {
+                expression: auto_clicker.new_schedule_expression.trim().to_string(),
+                run_minutes: auto_clicker.new_schedule_run_minutes,
+            });
+        auto_clicker.new_schedule_expression.clear();
+        auto_clicker.new_schedule_run_minutes = 0;
+    }
+
+    Command::none()
+}
@@ -0,0 +1,16 @@
+use crate::update::settings_file_changed::offer_reload;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() {
+        return Command::none();
+    }
+
+    if let Some(settings) = auto_clicker.deferred_reload.take() {
+        offer_reload(auto_clicker, settings);
+    }
+
+    Command::none()
+}
@@ -0,0 +1,20 @@
+use crate::settings;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+use std::time::Duration;
+
+/// How long the settings have to stay untouched before they are saved automatically.
+const AUTO_SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let settled = auto_clicker
+        .last_edit_at
+        .is_none_or(|last_edit_at| last_edit_at.elapsed() >= AUTO_SAVE_DEBOUNCE);
+
+    if auto_clicker.auto_save && settled && settings::has_unsaved_changes(auto_clicker) {
+        auto_clicker.settings_error = settings::save(auto_clicker).err();
+    }
+
+    Command::none()
+}
@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, enabled: bool) -> Command<Message> {
+    auto_clicker.profile.burst_pattern.enabled = enabled;
+    Command::none()
+}
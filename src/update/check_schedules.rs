@@ -0,0 +1,157 @@
+use crate::update::start::{self, RunTiming};
+use crate::update::Message;
+use crate::AutoClicker;
+use chrono::{DateTime, Local};
+use click_r_engine::schedule::RecurringSchedule;
+use iced::Command;
+use std::time::Duration;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let now = Local::now();
+    let Some(last_check) = auto_clicker.last_schedule_check.replace(now) else {
+        return Command::none();
+    };
+
+    let (started, skipped) = due_runs(
+        &auto_clicker.profile.recurring_schedules,
+        last_check,
+        now,
+        *auto_clicker.is_running.lock().unwrap(),
+    );
+
+    for (schedule, skipped_at) in skipped {
+        auto_clicker
+            .skipped_runs
+            .retain(|(skipped, _)| *skipped != schedule);
+        auto_clicker.skipped_runs.push((schedule, skipped_at));
+    }
+
+    let Some(run_minutes) = started.map(|schedule| schedule.run_minutes) else {
+        return Command::none();
+    };
+
+    let duration = if run_minutes == 0 {
+        let configured = auto_clicker.profile.duration_seconds
+            + auto_clicker.profile.duration_minutes * 60
+            + auto_clicker.profile.duration_hours * 3600;
+        (configured > 0).then_some(configured)
+    } else {
+        Some(run_minutes * 60)
+    };
+
+    start::start_with(
+        auto_clicker,
+        RunTiming {
+            delay_before_start: Duration::ZERO,
+            duration,
+            stop_at: None,
+        },
+    )
+}
+
+/// Splits the schedules that came due since the last check into the one to start and the
+/// skipped ones, with the time each was due.
+///
+/// A schedule is due once however many of its runs were missed. Runs that come due during
+/// another run are not started later, the list shows them as skipped.
+fn due_runs(
+    schedules: &[RecurringSchedule],
+    last_check: DateTime<Local>,
+    now: DateTime<Local>,
+    is_running: bool,
+) -> (
+    Option<RecurringSchedule>,
+    Vec<(RecurringSchedule, DateTime<Local>)>,
+) {
+    let mut due = schedules
+        .iter()
+        .filter_map(|schedule| {
+            schedule
+                .next_run_after(last_check)
+                .filter(|next_run| *next_run <= now)
+                .map(|next_run| (schedule.clone(), next_run))
+        })
+        .collect::<Vec<_>>();
+
+    let started = if is_running || due.is_empty() {
+        None
+    } else {
+        Some(due.remove(0).0)
+    };
+
+    (started, due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, 19, hour, minute, second)
+            .unwrap()
+    }
+
+    fn schedule(expression: &str, run_minutes: u64) -> RecurringSchedule {
+        RecurringSchedule {
+            expression: expression.to_string(),
+            run_minutes,
+        }
+    }
+
+    #[test]
+    fn finds_the_next_cron_run() {
+        let weekdays = schedule("55 8 * * MON-FRI", 0);
+
+        // 2026-10-19 is a Monday, the following Saturday and Sunday are skipped.
+        assert_eq!(weekdays.next_run_after(at(8, 0, 0)), Some(at(8, 55, 0)));
+        assert_eq!(
+            weekdays.next_run_after(at(8, 55, 0)),
+            Some(at(8, 55, 0) + chrono::Duration::days(1))
+        );
+        assert_eq!(
+            weekdays.next_run_after(at(9, 0, 0) + chrono::Duration::days(4)),
+            Some(at(8, 55, 0) + chrono::Duration::days(7))
+        );
+        assert_eq!(schedule("not cron", 0).next_run_after(at(8, 0, 0)), None);
+    }
+
+    #[test]
+    fn starts_a_run_that_came_due_since_the_last_check() {
+        let schedules = [schedule("30 9 * * *", 5)];
+
+        assert_eq!(
+            due_runs(&schedules, at(9, 29, 59), at(9, 30, 0), false),
+            (Some(schedules[0].clone()), Vec::new())
+        );
+        assert_eq!(
+            due_runs(&schedules, at(9, 30, 0), at(9, 30, 1), false),
+            (None, Vec::new())
+        );
+    }
+
+    #[test]
+    fn skips_runs_that_come_due_while_running() {
+        let schedules = [schedule("30 9 * * *", 5)];
+
+        assert_eq!(
+            due_runs(&schedules, at(9, 29, 59), at(9, 30, 0), true),
+            (None, vec![(schedules[0].clone(), at(9, 30, 0))])
+        );
+    }
+
+    #[test]
+    fn starts_one_missed_run_and_skips_the_others() {
+        let schedules = [schedule("0 * * * *", 0), schedule("30 9 * * *", 5)];
+
+        // Three hourly runs were missed while the computer slept, one of them is started.
+        assert_eq!(
+            due_runs(&schedules, at(7, 59, 0), at(10, 15, 0), false),
+            (
+                Some(schedules[0].clone()),
+                vec![(schedules[1].clone(), at(9, 30, 0))]
+            )
+        );
+    }
+}
@@ -3,6 +3,6 @@ use crate::AutoClicker;
 use iced::Command;
 
 pub fn handle(auto_clicker: &mut AutoClicker, new_clicks_count: u8) -> Command<Message> {
-    auto_clicker.clicks_count_slider_value = new_clicks_count;
+    auto_clicker.profile.clicks_count_slider_value = new_clicks_count;
     Command::none()
 }
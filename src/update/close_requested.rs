@@ -0,0 +1,18 @@
+use crate::settings;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::{window, Command};
+
+/// Saves pending edits before closing. If that fails the window stays open to show the error,
+/// closing it again discards the edits.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if settings::has_unsaved_changes(auto_clicker) && !auto_clicker.save_on_close_failed {
+        if let Err(error) = settings::save(auto_clicker) {
+            auto_clicker.settings_error = Some(error);
+            auto_clicker.save_on_close_failed = true;
+            return Command::none();
+        }
+    }
+
+    window::close(window::Id::MAIN)
+}
@@ -0,0 +1,80 @@
+use crate::control::{self, ControlCommand, ControlRequest, RunState};
+use crate::settings;
+use crate::update::{update_handler, Message};
+use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::schedule::resolve_start_stop;
+use iced::Command;
+use serde_json::json;
+
+/// Answers a command from a control connection, the commands that change something are handled
+/// like the message the window sends for them.
+pub fn handle(auto_clicker: &mut AutoClicker, request: ControlRequest) -> Command<Message> {
+    let ControlRequest {
+        command,
+        connection,
+    } = request;
+    let state = RunState::of(auto_clicker);
+
+    let refusal = match &command {
+        ControlCommand::Start if state != RunState::Idle => {
+            Some(String::from("A run is already in progress"))
+        }
+        ControlCommand::Stop | ControlCommand::Pause if state == RunState::Idle => {
+            Some(String::from("Nothing is running"))
+        }
+        ControlCommand::Pause if state == RunState::Paused => {
+            Some(String::from("The run is already paused"))
+        }
+        ControlCommand::Resume if state != RunState::Paused => {
+            Some(String::from("The run is not paused"))
+        }
+        ControlCommand::LoadProfile { .. } if state != RunState::Idle => {
+            Some(String::from("Profiles cannot be switched during a run"))
+        }
+        ControlCommand::LoadProfile { name } if !auto_clicker.profiles.contains_key(name) => {
+            Some(format!("No profile named \"{}\"", name))
+        }
+        ControlCommand::LoadProfile { name } => {
+            settings::profile_values(&auto_clicker.profiles, name)
+                .err()
+                .map(|error| error.to_string())
+        }
+        ControlCommand::Start | ControlCommand::Toggle if state == RunState::Idle => {
+            resolve_start_stop(
+                &auto_clicker.profile.scheduled_start,
+                &auto_clicker.profile.scheduled_stop,
+                Local::now(),
+            )
+            .err()
+            .map(|error| error.to_string())
+        }
+        _ => None,
+    };
+
+    if let Some(refusal) = refusal {
+        connection.error(refusal);
+        return Command::none();
+    }
+
+    match command.message() {
+        Some(message) => {
+            connection.ok();
+            update_handler(auto_clicker, message)
+        }
+        None => {
+            match command {
+                ControlCommand::Subscribe => {
+                    connection.ok();
+                    connection.send(control::state_event(RunState::of(auto_clicker)));
+                    auto_clicker.control_subscribers.push(connection);
+                }
+                _ => {
+                    connection.send(json!({ "ok": true, "status": control::status(auto_clicker) }));
+                }
+            }
+
+            Command::none()
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_name: String) -> Command<Message> {
+    if let Some(draft) = &mut auto_clicker.palette_draft {
+        draft.name = new_name;
+    }
+
+    Command::none()
+}
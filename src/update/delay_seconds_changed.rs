@@ -3,6 +3,6 @@ use crate::AutoClicker;
 use iced::Command;
 
 pub fn handle(auto_clicker: &mut AutoClicker, new_seconds: u64) -> Command<Message> {
-    auto_clicker.delay_seconds = new_seconds;
+    auto_clicker.profile.delay_seconds = new_seconds;
     Command::none()
 }
@@ -0,0 +1,21 @@
+use crate::settings::{load_profile, DEFAULT_PROFILE};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() || auto_clicker.active_profile == DEFAULT_PROFILE {
+        return Command::none();
+    }
+
+    let deleted = auto_clicker.active_profile.clone();
+
+    match load_profile(auto_clicker, DEFAULT_PROFILE) {
+        Ok(()) => {
+            auto_clicker.profiles.remove(&deleted);
+        }
+        Err(error) => auto_clicker.settings_error = Some(error),
+    }
+
+    Command::none()
+}
@@ -3,6 +3,6 @@ use crate::AutoClicker;
 use iced::Command;
 
 pub fn handle(auto_clicker: &mut AutoClicker, new_hours: u64) -> Command<Message> {
-    auto_clicker.duration_hours = new_hours;
+    auto_clicker.profile.duration_hours = new_hours;
     Command::none()
 }
@@ -0,0 +1,18 @@
+use crate::settings::{ProfileBundle, SettingsFile};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+use std::path::Path;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let path = auto_clicker.bundle_path.trim().to_string();
+    let profiles = SettingsFile::from_auto_clicker(auto_clicker).profiles;
+    let count = profiles.len();
+
+    auto_clicker.bundle_status = Some(
+        ProfileBundle::new(profiles, &auto_clicker.custom_themes)
+            .save(Path::new(&path))
+            .map(|_| format!("Exported {} profiles to {}", count, path)),
+    );
+    Command::none()
+}
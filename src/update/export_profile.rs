@@ -0,0 +1,22 @@
+use crate::settings::{snapshot, ProfileBundle};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Exports the active profile with its current, possibly unsaved, values.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let path = auto_clicker.bundle_path.trim().to_string();
+    let profiles = BTreeMap::from([(
+        auto_clicker.active_profile.clone(),
+        snapshot(&auto_clicker.profile),
+    )]);
+
+    auto_clicker.bundle_status = Some(
+        ProfileBundle::new(profiles, &auto_clicker.custom_themes)
+            .save(Path::new(&path))
+            .map(|_| format!("Exported \"{}\" to {}", auto_clicker.active_profile, path)),
+    );
+    Command::none()
+}
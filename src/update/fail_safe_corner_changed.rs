@@ -0,0 +1,9 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use click_r_engine::fail_safe::ScreenCorner;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, corner: ScreenCorner) -> Command<Message> {
+    auto_clicker.profile.fail_safe_corner = corner;
+    Command::none()
+}
@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_threshold: u32) -> Command<Message> {
+    auto_clicker.profile.fail_safe_mouse_move_threshold = new_threshold;
+    Command::none()
+}
@@ -0,0 +1,29 @@
+use crate::settings::{CollisionResolution, ProfileBundle};
+use crate::update::{resolve_import, Message};
+use crate::AutoClicker;
+use iced::Command;
+use std::path::Path;
+
+/// Imports right away unless a name is taken, then waits for the user to pick rename or overwrite.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() {
+        return Command::none();
+    }
+
+    let path = auto_clicker.bundle_path.trim().to_string();
+
+    match ProfileBundle::load(Path::new(&path)) {
+        Ok(bundle) => {
+            let has_collisions = !bundle.collisions(&auto_clicker.profiles).is_empty();
+            auto_clicker.pending_import = Some(bundle);
+            auto_clicker.bundle_status = None;
+
+            if !has_collisions {
+                return resolve_import::handle(auto_clicker, CollisionResolution::Rename);
+            }
+        }
+        Err(error) => auto_clicker.bundle_status = Some(Err(error)),
+    }
+
+    Command::none()
+}
@@ -0,0 +1,25 @@
+use crate::theme_import;
+use crate::update::save_custom_theme::add_custom_theme;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+use std::path::Path;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let path = auto_clicker.theme_import_path.trim().to_string();
+
+    if path.is_empty() {
+        return Command::none();
+    }
+
+    match theme_import::import(Path::new(&path)) {
+        Ok(custom) => {
+            add_custom_theme(auto_clicker, custom);
+            auto_clicker.theme_import_error = None;
+            auto_clicker.theme_import_path.clear();
+        }
+        Err(error) => auto_clicker.theme_import_error = Some(error),
+    }
+
+    Command::none()
+}
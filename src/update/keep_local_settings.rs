@@ -0,0 +1,13 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+/// Resolves a conflict by keeping the unsaved edits, they stay unsaved against the file on disk
+/// and the next save overwrites it.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if let Some(settings) = auto_clicker.settings_conflict.take() {
+        auto_clicker.saved_settings = settings.to_value();
+    }
+
+    Command::none()
+}
@@ -1,5 +1,7 @@
+use crate::panic_key;
 use crate::update::{start, stop, Message};
 use crate::AutoClicker;
+use click_r_engine::stop_reason::StopReason;
 use iced::keyboard::key::Named;
 use iced::keyboard::Key;
 use iced::Command;
@@ -7,6 +9,11 @@ use iced::Command;
 pub fn handle(auto_clicker: &mut AutoClicker, key_code: Key) -> Command<Message> {
     match key_code {
         Key::Named(name) => match name {
+            _ if panic_key::matches(auto_clicker.profile.panic_key, name) => {
+                let _ = stop::stop_with(auto_clicker, StopReason::PanicKey);
+                Command::none()
+            }
+
             Named::F6 => {
                 let _ = start::handle(auto_clicker);
                 Command::none()
@@ -0,0 +1,76 @@
+use crate::settings::switch_session_profile;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+/// Switches profile for a control request or a forwarded `--profile`, the settings file keeps
+/// the active profile it had.
+pub fn handle(auto_clicker: &mut AutoClicker, name: String) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() || name == auto_clicker.active_profile {
+        return Command::none();
+    }
+
+    auto_clicker.settings_error = switch_session_profile(auto_clicker, &name).err();
+    Command::none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{snapshot, SettingsFile};
+    use crate::Profile;
+    use serde_json::json;
+
+    fn app() -> AutoClicker {
+        let mut settings = SettingsFile::default();
+        let farming = Profile {
+            max_cycles: 7,
+            ..Profile::default()
+        };
+        settings
+            .profiles
+            .insert("Farming".to_string(), snapshot(&farming));
+        settings
+            .profiles
+            .insert("Broken".to_string(), json!({"max_cycles": "lots"}));
+        settings.into_auto_clicker().0
+    }
+
+    #[test]
+    fn keeps_the_saved_active_profile() {
+        let mut auto_clicker = app();
+        let opened_with = auto_clicker.active_profile.clone();
+
+        let _ = handle(&mut auto_clicker, "Farming".to_string());
+
+        assert_eq!(auto_clicker.active_profile, "Farming");
+        assert_eq!(auto_clicker.profile.max_cycles, 7);
+        assert!(auto_clicker.settings_error.is_none());
+        let saved = SettingsFile::from_auto_clicker(&auto_clicker);
+        assert_eq!(saved.active_profile, opened_with);
+    }
+
+    #[test]
+    fn switching_back_forgets_the_session_profile() {
+        let mut auto_clicker = app();
+        let opened_with = auto_clicker.active_profile.clone();
+
+        let _ = handle(&mut auto_clicker, "Farming".to_string());
+        let _ = handle(&mut auto_clicker, opened_with.clone());
+
+        assert_eq!(auto_clicker.active_profile, opened_with);
+        assert!(auto_clicker.remembered_profile.is_none());
+    }
+
+    #[test]
+    fn a_broken_profile_leaves_the_active_one() {
+        let mut auto_clicker = app();
+        let opened_with = auto_clicker.active_profile.clone();
+
+        let _ = handle(&mut auto_clicker, "Broken".to_string());
+
+        assert_eq!(auto_clicker.active_profile, opened_with);
+        assert!(auto_clicker.remembered_profile.is_none());
+        assert!(auto_clicker.settings_error.is_some());
+    }
+}
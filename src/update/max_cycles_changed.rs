@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_max_cycles: u64) -> Command<Message> {
+    auto_clicker.profile.max_cycles = new_max_cycles;
+    Command::none()
+}
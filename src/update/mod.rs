@@ -1,30 +1,137 @@
+mod add_phase;
+mod add_recurring_schedule;
+mod apply_deferred_reload;
+mod auto_save_tick;
+mod auto_save_toggled;
+mod bundle_path_changed;
+mod burst_pattern_toggled;
+mod cancel_import;
+mod check_schedules;
 mod click_count_slider_changed;
+mod close_requested;
+mod control_request;
+mod cps_mode_toggled;
+mod create_profile;
+mod custom_theme_name_changed;
 mod delay_hours_changed;
 mod delay_minutes_changed;
 mod delay_seconds_changed;
+mod delete_profile;
+mod dismiss_settings_error;
+mod duplicate_profile;
 mod duration_hours_changed;
 mod duration_minutes_changed;
 mod duration_seconds_changed;
+mod export_all_profiles;
+mod export_profile;
+mod fail_safe_corner_changed;
+mod fail_safe_mouse_move_changed;
+mod import_profiles;
+mod import_theme;
 mod interval_slider_changed;
+mod keep_local_settings;
 mod key_pressed;
+mod load_session_profile;
+mod max_cycles_changed;
+mod max_total_clicks_changed;
+mod new_schedule_expression_changed;
+mod new_schedule_run_minutes_changed;
+mod palette_color_changed;
+mod palette_editor_toggled;
+mod panic_key_changed;
+mod pattern_repeat_changed;
+mod pause;
+mod phase_changed;
+mod poll_run_events;
+mod profile_name_input_changed;
+mod profile_selected;
+mod raise_window;
+mod reload_settings;
+mod remove_phase;
+mod remove_recurring_schedule;
+mod rename_profile;
 mod reset_to_defaults;
+mod resolve_import;
+mod resume;
+mod save_custom_theme;
 mod save_settings;
+mod scheduled_start_changed;
+mod scheduled_stop_changed;
 mod select_mouse_button;
+mod settings_file_changed;
 mod start;
 mod stop;
+mod target_cps_changed;
 mod theme_changed;
+mod theme_import_path_changed;
 mod tick;
+mod toggle;
 
 use crate::message::Message;
+use crate::settings;
 use crate::AutoClicker;
+use click_r_engine::ClickConfig;
 use iced::Command;
 
+/// Handles `message`, then applies click setting edits to the run in progress and notes unsaved
+/// changes, unless the message cannot edit anything.
 pub fn update_handler(auto_clicker: &mut AutoClicker, message: Message) -> Command<Message> {
+    if !message.can_edit_settings() {
+        return dispatch(auto_clicker, message);
+    }
+
+    let command = dispatch(auto_clicker, message);
+
+    if let Some(runner) = &auto_clicker.runner {
+        let clicks = ClickConfig::from(&*auto_clicker);
+
+        if runner.clicks() != clicks {
+            runner.set_clicks(clicks);
+        }
+    }
+
+    settings::track_changes(auto_clicker);
+    command
+}
+
+fn dispatch(auto_clicker: &mut AutoClicker, message: Message) -> Command<Message> {
     match message {
+        Message::ProfileSelected(name) => profile_selected::handle(auto_clicker, name),
+        Message::LoadSessionProfile(name) => load_session_profile::handle(auto_clicker, name),
+        Message::ProfileNameInputChanged(new_name) => {
+            profile_name_input_changed::handle(auto_clicker, new_name)
+        }
+        Message::CreateProfile => create_profile::handle(auto_clicker),
+        Message::DuplicateProfile => duplicate_profile::handle(auto_clicker),
+        Message::RenameProfile => rename_profile::handle(auto_clicker),
+        Message::DeleteProfile => delete_profile::handle(auto_clicker),
+        Message::BundlePathChanged(new_path) => bundle_path_changed::handle(auto_clicker, new_path),
+        Message::ExportProfile => export_profile::handle(auto_clicker),
+        Message::ExportAllProfiles => export_all_profiles::handle(auto_clicker),
+        Message::ImportProfiles => import_profiles::handle(auto_clicker),
+        Message::ResolveImport(resolution) => resolve_import::handle(auto_clicker, resolution),
+        Message::CancelImport => cancel_import::handle(auto_clicker),
         Message::ThemeChanged(theme) => theme_changed::handle(auto_clicker, theme),
+        Message::PaletteEditorToggled(open) => palette_editor_toggled::handle(auto_clicker, open),
+        Message::PaletteColorChanged(color, value) => {
+            palette_color_changed::handle(auto_clicker, color, value)
+        }
+        Message::CustomThemeNameChanged(new_name) => {
+            custom_theme_name_changed::handle(auto_clicker, new_name)
+        }
+        Message::SaveCustomTheme => save_custom_theme::handle(auto_clicker),
+        Message::ThemeImportPathChanged(new_path) => {
+            theme_import_path_changed::handle(auto_clicker, new_path)
+        }
+        Message::ImportTheme => import_theme::handle(auto_clicker),
         Message::SelectMouseButton(button) => select_mouse_button::handle(auto_clicker, button),
         Message::Start => start::handle(auto_clicker),
         Message::Stop => stop::handle(auto_clicker),
+        Message::Toggle => toggle::handle(auto_clicker),
+        Message::Pause => pause::handle(auto_clicker),
+        Message::Resume => resume::handle(auto_clicker),
+        Message::PollRunEvents => poll_run_events::handle(auto_clicker),
+        Message::Control(request) => control_request::handle(auto_clicker, request),
         Message::Tick => tick::handle(auto_clicker),
         Message::ResetToDefaults => reset_to_defaults::handle(auto_clicker),
         Message::IntervalSliderChanged(new_interval) => {
@@ -33,6 +140,10 @@ pub fn update_handler(auto_clicker: &mut AutoClicker, message: Message) -> Comma
         Message::ClickCountSliderChanged(new_clicks_count) => {
             click_count_slider_changed::handle(auto_clicker, new_clicks_count)
         }
+        Message::CpsModeToggled(enabled) => cps_mode_toggled::handle(auto_clicker, enabled),
+        Message::TargetCpsChanged(new_target_cps) => {
+            target_cps_changed::handle(auto_clicker, new_target_cps)
+        }
         Message::DelayHoursChanged(new_hours) => {
             delay_hours_changed::handle(auto_clicker, new_hours)
         }
@@ -51,7 +162,55 @@ pub fn update_handler(auto_clicker: &mut AutoClicker, message: Message) -> Comma
         Message::DurationSecondsChanged(new_seconds) => {
             duration_seconds_changed::handle(auto_clicker, new_seconds)
         }
+        Message::FailSafeCornerChanged(corner) => {
+            fail_safe_corner_changed::handle(auto_clicker, corner)
+        }
+        Message::FailSafeMouseMoveChanged(threshold) => {
+            fail_safe_mouse_move_changed::handle(auto_clicker, threshold)
+        }
+        Message::MaxTotalClicksChanged(new_max_total_clicks) => {
+            max_total_clicks_changed::handle(auto_clicker, new_max_total_clicks)
+        }
+        Message::MaxCyclesChanged(new_max_cycles) => {
+            max_cycles_changed::handle(auto_clicker, new_max_cycles)
+        }
+        Message::PanicKeyChanged(key) => panic_key_changed::handle(auto_clicker, key),
+        Message::ScheduledStartChanged(new_scheduled_start) => {
+            scheduled_start_changed::handle(auto_clicker, new_scheduled_start)
+        }
+        Message::ScheduledStopChanged(new_scheduled_stop) => {
+            scheduled_stop_changed::handle(auto_clicker, new_scheduled_stop)
+        }
+        Message::NewScheduleExpressionChanged(new_expression) => {
+            new_schedule_expression_changed::handle(auto_clicker, new_expression)
+        }
+        Message::NewScheduleRunMinutesChanged(new_run_minutes) => {
+            new_schedule_run_minutes_changed::handle(auto_clicker, new_run_minutes)
+        }
+        Message::AddRecurringSchedule => add_recurring_schedule::handle(auto_clicker),
+        Message::RemoveRecurringSchedule(index) => {
+            remove_recurring_schedule::handle(auto_clicker, index)
+        }
+        Message::CheckSchedules => check_schedules::handle(auto_clicker),
+        Message::BurstPatternToggled(enabled) => {
+            burst_pattern_toggled::handle(auto_clicker, enabled)
+        }
+        Message::PatternRepeatChanged(new_repeat) => {
+            pattern_repeat_changed::handle(auto_clicker, new_repeat)
+        }
+        Message::AddPhase => add_phase::handle(auto_clicker),
+        Message::PhaseChanged(index, phase) => phase_changed::handle(auto_clicker, index, phase),
+        Message::RemovePhase(index) => remove_phase::handle(auto_clicker, index),
         Message::SaveSettings => save_settings::handle(auto_clicker),
+        Message::AutoSaveToggled(enabled) => auto_save_toggled::handle(auto_clicker, enabled),
+        Message::AutoSaveTick => auto_save_tick::handle(auto_clicker),
+        Message::CloseRequested => close_requested::handle(auto_clicker),
+        Message::RaiseWindow => raise_window::handle(),
+        Message::DismissSettingsError => dismiss_settings_error::handle(auto_clicker),
+        Message::SettingsFileChanged => settings_file_changed::handle(auto_clicker),
+        Message::ApplyDeferredReload => apply_deferred_reload::handle(auto_clicker),
+        Message::ReloadSettings => reload_settings::handle(auto_clicker),
+        Message::KeepLocalSettings => keep_local_settings::handle(auto_clicker),
         Message::KeyPressed(key) => key_pressed::handle(auto_clicker, key),
         Message::None => Command::none(),
     }
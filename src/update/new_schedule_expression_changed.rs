@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_expression: String) -> Command<Message> {
+    auto_clicker.new_schedule_expression = new_expression;
+    Command::none()
+}
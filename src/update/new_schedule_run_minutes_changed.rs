@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_run_minutes: u64) -> Command<Message> {
+    auto_clicker.new_schedule_run_minutes = new_run_minutes;
+    Command::none()
+}
@@ -0,0 +1,16 @@
+use crate::theme::PaletteColor;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(
+    auto_clicker: &mut AutoClicker,
+    color: PaletteColor,
+    value: String,
+) -> Command<Message> {
+    if let Some(draft) = &mut auto_clicker.palette_draft {
+        draft.colors[color as usize] = value;
+    }
+
+    Command::none()
+}
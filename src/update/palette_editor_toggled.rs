@@ -0,0 +1,10 @@
+use crate::theme::PaletteDraft;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, open: bool) -> Command<Message> {
+    auto_clicker.palette_draft =
+        open.then(|| PaletteDraft::from_theme(&auto_clicker.profile.theme));
+    Command::none()
+}
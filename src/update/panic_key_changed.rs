@@ -0,0 +1,9 @@
+use crate::panic_key::PanicKey;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, key: PanicKey) -> Command<Message> {
+    auto_clicker.profile.panic_key = key;
+    Command::none()
+}
@@ -0,0 +1,12 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use click_r_engine::pattern::Phase;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, index: usize, phase: Phase) -> Command<Message> {
+    if let Some(existing) = auto_clicker.profile.burst_pattern.phases.get_mut(index) {
+        *existing = phase;
+    }
+
+    Command::none()
+}
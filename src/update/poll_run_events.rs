@@ -0,0 +1,43 @@
+use crate::control;
+use crate::update::Message;
+use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::RunEvent;
+use iced::Command;
+use std::sync::mpsc::TryRecvError;
+
+/// Passes the events of the current run on to the control connections that subscribed to them.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let Some(events) = &auto_clicker.run_events else {
+        control::announce_state(auto_clicker);
+        return Command::none();
+    };
+
+    let finished = loop {
+        let event = match events.try_recv() {
+            Ok(event) => event,
+            Err(TryRecvError::Empty) => break false,
+            Err(TryRecvError::Disconnected) => break true,
+        };
+
+        // A pause during the start delay moves the start back.
+        if let RunEvent::Started = event {
+            auto_clicker.run_starts_at = Some(Local::now());
+        }
+
+        auto_clicker
+            .control_subscribers
+            .retain(|connection| connection.send(control::event(event)));
+
+        if let RunEvent::Finished(_) = event {
+            break true;
+        }
+    };
+
+    if finished {
+        auto_clicker.run_events = None;
+    }
+
+    control::announce_state(auto_clicker);
+    Command::none()
+}
@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_name: String) -> Command<Message> {
+    auto_clicker.profile_name_input = new_name;
+    Command::none()
+}
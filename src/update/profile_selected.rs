@@ -0,0 +1,15 @@
+use crate::settings::{remember_active_profile, switch_profile};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, name: String) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() || name == auto_clicker.active_profile {
+        return Command::none();
+    }
+
+    auto_clicker.settings_error = switch_profile(auto_clicker, &name)
+        .and_then(|()| remember_active_profile(auto_clicker))
+        .err();
+    Command::none()
+}
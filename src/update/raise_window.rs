@@ -0,0 +1,10 @@
+use crate::update::Message;
+use iced::{window, Command};
+
+/// Brings the window to the front, for a second launch without anything else to do.
+pub fn handle() -> Command<Message> {
+    Command::batch([
+        window::minimize(window::Id::MAIN, false),
+        window::gain_focus(window::Id::MAIN),
+    ])
+}
@@ -0,0 +1,17 @@
+use crate::settings::apply_external;
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+/// Resolves a conflict by dropping the unsaved edits in favor of the settings file.
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() {
+        return Command::none();
+    }
+
+    if let Some(settings) = auto_clicker.settings_conflict.take() {
+        apply_external(auto_clicker, settings);
+    }
+
+    Command::none()
+}
@@ -0,0 +1,11 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, index: usize) -> Command<Message> {
+    if index < auto_clicker.profile.burst_pattern.phases.len() {
+        auto_clicker.profile.burst_pattern.phases.remove(index);
+    }
+
+    Command::none()
+}
@@ -0,0 +1,11 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, index: usize) -> Command<Message> {
+    if index < auto_clicker.profile.recurring_schedules.len() {
+        auto_clicker.profile.recurring_schedules.remove(index);
+    }
+
+    Command::none()
+}
@@ -0,0 +1,23 @@
+use crate::settings::{snapshot, DEFAULT_PROFILE};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let name = auto_clicker.profile_name_input.trim().to_string();
+
+    if *auto_clicker.is_running.lock().unwrap()
+        || auto_clicker.active_profile == DEFAULT_PROFILE
+        || name.is_empty()
+        || auto_clicker.profiles.contains_key(&name)
+    {
+        return Command::none();
+    }
+
+    auto_clicker.profiles.remove(&auto_clicker.active_profile);
+    auto_clicker
+        .profiles
+        .insert(name.clone(), snapshot(&auto_clicker.profile));
+    auto_clicker.active_profile = name;
+    Command::none()
+}
@@ -1,8 +1,9 @@
+use crate::settings::replace_profile_values;
 use crate::update::Message;
-use crate::AutoClicker;
+use crate::{AutoClicker, Profile};
 use iced::Command;
 
 pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
-    *auto_clicker = AutoClicker::default();
+    replace_profile_values(auto_clicker, Profile::default());
     Command::none()
 }
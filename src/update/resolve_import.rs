@@ -0,0 +1,28 @@
+use crate::settings::{load_profile, CollisionResolution};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, resolution: CollisionResolution) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() {
+        return Command::none();
+    }
+
+    let Some(bundle) = auto_clicker.pending_import.take() else {
+        return Command::none();
+    };
+
+    let imported = bundle.merge_into(
+        &mut auto_clicker.profiles,
+        &mut auto_clicker.custom_themes,
+        resolution,
+    );
+
+    if imported.contains(&auto_clicker.active_profile) {
+        let active_profile = auto_clicker.active_profile.clone();
+        auto_clicker.settings_error = load_profile(auto_clicker, &active_profile).err();
+    }
+
+    auto_clicker.bundle_status = Some(Ok(format!("Imported {}", imported.join(", "))));
+    Command::none()
+}
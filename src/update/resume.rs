@@ -0,0 +1,11 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if let Some(runner) = &auto_clicker.runner {
+        runner.resume();
+    }
+
+    Command::none()
+}
@@ -0,0 +1,36 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::theme::Custom;
+use iced::{Command, Theme};
+use std::sync::Arc;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    let Some(Ok(custom)) = auto_clicker
+        .palette_draft
+        .as_ref()
+        .map(|draft| draft.to_custom(&auto_clicker.custom_themes))
+    else {
+        return Command::none();
+    };
+
+    add_custom_theme(auto_clicker, custom);
+    auto_clicker.palette_draft = None;
+    Command::none()
+}
+
+/// Adds a custom theme and switches to it, a custom theme with the same name is replaced.
+pub fn add_custom_theme(auto_clicker: &mut AutoClicker, custom: Custom) {
+    let custom = Arc::new(custom);
+    let name = custom.to_string();
+
+    match auto_clicker
+        .custom_themes
+        .iter_mut()
+        .find(|existing| existing.to_string() == name)
+    {
+        Some(existing) => *existing = Arc::clone(&custom),
+        None => auto_clicker.custom_themes.push(Arc::clone(&custom)),
+    }
+
+    auto_clicker.profile.theme = Theme::Custom(custom);
+}
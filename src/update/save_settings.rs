@@ -1,10 +1,9 @@
+use crate::settings;
 use crate::update::Message;
 use crate::AutoClicker;
 use iced::Command;
-use std::fs;
 
 pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
-    let settings = serde_json::to_string(auto_clicker).unwrap();
-    fs::write("settings.json", settings).expect("Unable to write settings to file");
+    auto_clicker.settings_error = settings::save(auto_clicker).err();
     Command::none()
 }
@@ -0,0 +1,9 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_scheduled_start: String) -> Command<Message> {
+    auto_clicker.profile.scheduled_start = new_scheduled_start;
+    auto_clicker.schedule_error = None;
+    Command::none()
+}
@@ -0,0 +1,9 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_scheduled_stop: String) -> Command<Message> {
+    auto_clicker.profile.scheduled_stop = new_scheduled_stop;
+    auto_clicker.schedule_error = None;
+    Command::none()
+}
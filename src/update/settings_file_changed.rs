@@ -0,0 +1,144 @@
+use crate::settings::{self, apply_external, has_unsaved_changes, SettingsFile};
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if !settings::path().exists() {
+        return Command::none();
+    }
+
+    match SettingsFile::reload() {
+        Ok(settings) => changed_on_disk(auto_clicker, settings),
+        Err(error) => auto_clicker.settings_error = Some(error),
+    }
+
+    Command::none()
+}
+
+fn changed_on_disk(auto_clicker: &mut AutoClicker, settings: SettingsFile) {
+    // Our own saves trigger the watcher too, they match what was saved last.
+    if settings.to_value() != auto_clicker.saved_settings {
+        offer_reload(auto_clicker, settings);
+    }
+}
+
+/// Applies settings changed on disk right away, once the running job ends, or after the user
+/// picked between them and their own unsaved edits.
+pub fn offer_reload(auto_clicker: &mut AutoClicker, settings: SettingsFile) {
+    if *auto_clicker.is_running.lock().unwrap() {
+        auto_clicker.deferred_reload = Some(settings);
+    } else if has_unsaved_changes(auto_clicker) {
+        auto_clicker.settings_conflict = Some(settings);
+    } else {
+        auto_clicker.settings_conflict = None;
+        apply_external(auto_clicker, settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::track_changes;
+    use crate::update::{apply_deferred_reload, keep_local_settings, reload_settings};
+
+    fn app() -> AutoClicker {
+        SettingsFile::default().into_auto_clicker().0
+    }
+
+    /// The settings of `auto_clicker` as another program would write them, with `max_cycles` of
+    /// the active profile changed.
+    fn external_change(auto_clicker: &AutoClicker, max_cycles: u64) -> SettingsFile {
+        let mut settings = SettingsFile::from_auto_clicker(auto_clicker);
+        settings
+            .profiles
+            .get_mut(&auto_clicker.active_profile)
+            .unwrap()["max_cycles"] = max_cycles.into();
+        settings
+    }
+
+    fn edit(auto_clicker: &mut AutoClicker, max_cycles: u64) {
+        auto_clicker.profile.max_cycles = max_cycles;
+        track_changes(auto_clicker);
+    }
+
+    #[test]
+    fn ignores_its_own_save() {
+        let mut auto_clicker = app();
+        edit(&mut auto_clicker, 5);
+        let saved = SettingsFile::from_auto_clicker(&auto_clicker);
+        auto_clicker.saved_settings = saved.to_value();
+
+        changed_on_disk(&mut auto_clicker, saved);
+
+        assert!(auto_clicker.settings_conflict.is_none());
+        assert_eq!(auto_clicker.profile.max_cycles, 5);
+        assert!(!has_unsaved_changes(&auto_clicker));
+    }
+
+    #[test]
+    fn applies_an_external_change_while_clean() {
+        let mut auto_clicker = app();
+        let settings = external_change(&auto_clicker, 7);
+
+        changed_on_disk(&mut auto_clicker, settings);
+
+        assert!(auto_clicker.settings_conflict.is_none());
+        assert_eq!(auto_clicker.profile.max_cycles, 7);
+        assert!(!has_unsaved_changes(&auto_clicker));
+    }
+
+    #[test]
+    fn offers_an_external_change_while_dirty() {
+        let mut auto_clicker = app();
+        edit(&mut auto_clicker, 5);
+        let settings = external_change(&auto_clicker, 7);
+
+        changed_on_disk(&mut auto_clicker, settings);
+
+        assert!(auto_clicker.settings_conflict.is_some());
+        assert_eq!(auto_clicker.profile.max_cycles, 5);
+    }
+
+    #[test]
+    fn reloads_or_keeps_the_local_edits_after_a_conflict() {
+        let mut reloaded = app();
+        edit(&mut reloaded, 5);
+        let settings = external_change(&reloaded, 7);
+        changed_on_disk(&mut reloaded, settings);
+        let _ = reload_settings::handle(&mut reloaded);
+
+        assert!(reloaded.settings_conflict.is_none());
+        assert_eq!(reloaded.profile.max_cycles, 7);
+        assert!(!has_unsaved_changes(&reloaded));
+
+        let mut kept = app();
+        edit(&mut kept, 5);
+        let settings = external_change(&kept, 7);
+        changed_on_disk(&mut kept, settings);
+        let _ = keep_local_settings::handle(&mut kept);
+
+        assert!(kept.settings_conflict.is_none());
+        assert_eq!(kept.profile.max_cycles, 5);
+        assert!(has_unsaved_changes(&kept));
+    }
+
+    #[test]
+    fn defers_an_external_change_until_the_run_ends() {
+        let mut auto_clicker = app();
+        *auto_clicker.is_running.lock().unwrap() = true;
+
+        let settings = external_change(&auto_clicker, 7);
+        changed_on_disk(&mut auto_clicker, settings);
+        let _ = apply_deferred_reload::handle(&mut auto_clicker);
+
+        assert!(auto_clicker.deferred_reload.is_some());
+        assert_eq!(auto_clicker.profile.max_cycles, 0);
+
+        *auto_clicker.is_running.lock().unwrap() = false;
+        let _ = apply_deferred_reload::handle(&mut auto_clicker);
+
+        assert!(auto_clicker.deferred_reload.is_none());
+        assert_eq!(auto_clicker.profile.max_cycles, 7);
+    }
+}
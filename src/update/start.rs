@@ -1,69 +1,108 @@
+use crate::control;
 use crate::update::Message;
 use crate::AutoClicker;
-use enigo::{Direction::Click, Enigo, Mouse, Settings as EnigoSettings};
+use chrono::{DateTime, Local};
+use click_r_engine::fail_safe::FailSafe;
+use click_r_engine::schedule::{resolve_start_stop, until};
+use click_r_engine::{ClickConfig, ClickJob, Runner, Schedule};
 use iced::Command;
-use std::sync::{mpsc, Arc, Mutex};
-use std::thread;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// When a run begins and what may end it besides the click and cycle limits.
+pub struct RunTiming {
+    pub delay_before_start: Duration,
+    /// Run length in seconds, `None` runs until another stop condition is met.
+    pub duration: Option<u64>,
+    pub stop_at: Option<DateTime<Local>>,
+}
 
 pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
-    *auto_clicker.is_running.lock().unwrap() = true;
-    auto_clicker.delay_timer = 0;
-    auto_clicker.time_running = 0;
-    auto_clicker.ticks_count = 0;
-    auto_clicker.total_clicks = Arc::new(Mutex::new(0));
-    let interval = auto_clicker.click_interval_slider_value;
-    let clicks_count = auto_clicker.clicks_count_slider_value;
-    let (tx, rx) = mpsc::channel();
-    let total_clicks = Arc::clone(&auto_clicker.total_clicks);
-    let selected_mouse_button = Arc::clone(&auto_clicker.selected_mouse_button);
-    let is_running = Arc::clone(&auto_clicker.is_running);
+    let now = Local::now();
 
-    let delay_before_start = auto_clicker.delay_seconds
-        + auto_clicker.delay_minutes * 60
-        + auto_clicker.delay_hours * 3600;
+    let (scheduled_start, stop_at) = match resolve_start_stop(
+        &auto_clicker.profile.scheduled_start,
+        &auto_clicker.profile.scheduled_stop,
+        now,
+    ) {
+        Ok(times) => times,
+        Err(error) => {
+            auto_clicker.schedule_error = Some(error);
+            return Command::none();
+        }
+    };
+    auto_clicker.schedule_error = None;
+
+    let delay_before_start = match scheduled_start {
+        Some(scheduled_start) => until(scheduled_start, now),
+        None => Duration::from_secs(
+            auto_clicker.profile.delay_seconds
+                + auto_clicker.profile.delay_minutes * 60
+                + auto_clicker.profile.delay_hours * 3600,
+        ),
+    };
 
-    let duration = if auto_clicker.duration_seconds == 0
-        && auto_clicker.duration_minutes == 0
-        && auto_clicker.duration_hours == 0
+    let duration = if auto_clicker.profile.duration_seconds == 0
+        && auto_clicker.profile.duration_minutes == 0
+        && auto_clicker.profile.duration_hours == 0
     {
         None
     } else {
         Some(
-            auto_clicker.duration_seconds
-                + auto_clicker.duration_minutes * 60
-                + auto_clicker.duration_hours * 3600,
+            auto_clicker.profile.duration_seconds
+                + auto_clicker.profile.duration_minutes * 60
+                + auto_clicker.profile.duration_hours * 3600,
         )
     };
 
-    auto_clicker.stop_sender = Some(tx);
-
-    let handle = thread::spawn(move || {
-        thread::park_timeout(Duration::from_secs(delay_before_start));
-        let mut enigo = Enigo::new(&EnigoSettings::default()).unwrap();
-        let button = selected_mouse_button.lock().unwrap().clone();
-        let end_time = std::time::Instant::now() + Duration::from_secs(duration.unwrap_or(0));
+    start_with(
+        auto_clicker,
+        RunTiming {
+            delay_before_start,
+            duration,
+            stop_at,
+        },
+    )
+}
 
-        loop {
-            if rx.try_recv().is_ok() {
-                *is_running.lock().unwrap() = false;
-                break;
-            }
+pub fn start_with(auto_clicker: &mut AutoClicker, timing: RunTiming) -> Command<Message> {
+    auto_clicker.delay_timer = 0;
+    auto_clicker.time_running = 0;
 
-            if duration.is_some() && std::time::Instant::now() >= end_time {
-                *is_running.lock().unwrap() = false;
-                break;
-            }
+    let now = Local::now();
+    auto_clicker.run_starts_at = Some(now + timing.delay_before_start);
 
-            for _ in 0..clicks_count {
-                enigo.button(button, Click).unwrap();
-                *total_clicks.lock().unwrap() += 1;
-            }
+    let job = ClickJob {
+        clicks: ClickConfig::from(&*auto_clicker),
+        burst_pattern: auto_clicker.profile.burst_pattern.clone(),
+        duration: timing.duration.map(Duration::from_secs),
+        max_total_clicks: auto_clicker.profile.max_total_clicks,
+        max_cycles: auto_clicker.profile.max_cycles,
+        fail_safe: FailSafe {
+            corner: auto_clicker.profile.fail_safe_corner,
+            mouse_move_threshold: auto_clicker.profile.fail_safe_mouse_move_threshold,
+            panic_key: auto_clicker.profile.panic_key,
+        },
+        dry_run: auto_clicker.dry_run,
+    };
+    let schedule = Schedule {
+        start_after: timing.delay_before_start,
+        stop_at: timing
+            .stop_at
+            .map(|stop_at| Instant::now() + until(stop_at, now)),
+    };
 
-            thread::park_timeout(Duration::from_secs(interval as u64));
-        }
-    });
+    let runner = Runner::start(job, schedule);
+    let progress = runner.progress();
+    auto_clicker.is_running = Arc::clone(&progress.running);
+    auto_clicker.is_paused = Arc::clone(&progress.paused);
+    auto_clicker.total_clicks = Arc::clone(&progress.total_clicks);
+    auto_clicker.stop_reason = Arc::clone(&progress.stop_reason);
+    auto_clicker.phase_status = Arc::clone(&progress.phase_status);
+    auto_clicker.clicking_started_at = Arc::clone(&progress.clicking_started_at);
+    auto_clicker.run_events = Some(runner.subscribe());
+    auto_clicker.runner = Some(runner);
 
-    auto_clicker.click_thread = Some(handle);
+    control::announce_state(auto_clicker);
     Command::none()
 }
@@ -1,16 +1,19 @@
+use crate::control;
 use crate::update::Message;
 use crate::AutoClicker;
+use click_r_engine::stop_reason::StopReason;
 use iced::Command;
 
 pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
-    if let Some(sender) = auto_clicker.stop_sender.take() {
-        if sender.send(()).is_ok() {
-            if let Some(handle) = auto_clicker.click_thread.take() {
-                handle.thread().unpark();
-                handle.join().unwrap();
-            }
-        }
+    stop_with(auto_clicker, StopReason::User)
+}
+
+pub fn stop_with(auto_clicker: &mut AutoClicker, reason: StopReason) -> Command<Message> {
+    if let Some(runner) = auto_clicker.runner.take() {
+        runner.stop(reason);
+        runner.join();
     }
 
+    control::announce_state(auto_clicker);
     Command::none()
 }
@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_target_cps: String) -> Command<Message> {
+    auto_clicker.profile.target_cps = new_target_cps;
+    Command::none()
+}
@@ -3,6 +3,6 @@ use crate::AutoClicker;
 use iced::{Command, Theme};
 
 pub fn handle(auto_clicker: &mut AutoClicker, theme: Theme) -> Command<Message> {
-    auto_clicker.theme = theme;
+    auto_clicker.profile.theme = theme;
     Command::none()
 }
@@ -0,0 +1,8 @@
+use crate::update::Message;
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker, new_path: String) -> Command<Message> {
+    auto_clicker.theme_import_path = new_path;
+    Command::none()
+}
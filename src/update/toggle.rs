@@ -0,0 +1,11 @@
+use crate::update::{start, stop, Message};
+use crate::AutoClicker;
+use iced::Command;
+
+pub fn handle(auto_clicker: &mut AutoClicker) -> Command<Message> {
+    if *auto_clicker.is_running.lock().unwrap() {
+        stop::handle(auto_clicker)
+    } else {
+        start::handle(auto_clicker)
+    }
+}
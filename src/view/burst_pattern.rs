@@ -0,0 +1,115 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use click_r_engine::pattern::{Phase, PhaseKind};
+use iced::theme::Button;
+use iced::widget::{button, checkbox, column, pick_list, row, text, text_input, Column};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let pattern = &auto_clicker.profile.burst_pattern;
+
+    let phases = pattern.phases.iter().copied().enumerate().fold(
+        Column::new().spacing(10),
+        |phases, (index, phase)| {
+            let mut interval_input = text_input("Interval", &phase.interval_ms.to_string());
+            let mut clicks_input = text_input("Clicks", &phase.clicks_count.to_string());
+
+            if phase.kind == PhaseKind::Work {
+                interval_input = interval_input.on_input(move |s| {
+                    Message::PhaseChanged(
+                        index,
+                        Phase {
+                            interval_ms: s.parse::<u64>().unwrap_or(1).max(1),
+                            ..phase
+                        },
+                    )
+                });
+                clicks_input = clicks_input.on_input(move |s| {
+                    Message::PhaseChanged(
+                        index,
+                        Phase {
+                            clicks_count: s.parse::<u8>().unwrap_or(1).max(1),
+                            ..phase
+                        },
+                    )
+                });
+            }
+
+            phases.push(
+                row![
+                    pick_list(PhaseKind::ALL, Some(phase.kind), move |kind| {
+                        Message::PhaseChanged(index, Phase { kind, ..phase })
+                    })
+                    .width(Length::FillPortion(2)),
+                    text("Length s:").width(Length::FillPortion(1)),
+                    text_input("Seconds", &phase.length_seconds.to_string())
+                        .on_input(move |s| {
+                            Message::PhaseChanged(
+                                index,
+                                Phase {
+                                    length_seconds: s.parse::<u64>().unwrap_or(0),
+                                    ..phase
+                                },
+                            )
+                        })
+                        .width(Length::FillPortion(1)),
+                    text("Every ms:").width(Length::FillPortion(1)),
+                    interval_input.width(Length::FillPortion(1)),
+                    text("Clicks:").width(Length::FillPortion(1)),
+                    clicks_input.width(Length::FillPortion(1)),
+                    button(text("Remove").horizontal_alignment(alignment::Horizontal::Center))
+                        .on_press(Message::RemovePhase(index))
+                        .style(Button::Destructive)
+                        .width(Length::FillPortion(1)),
+                ]
+                .align_items(Alignment::Center)
+                .spacing(10),
+            )
+        },
+    );
+
+    row![
+        row![
+            text("Burst pattern:").width(Length::FillPortion(1)),
+            text(if pattern.enabled {
+                format!(
+                    "{} phases, {}",
+                    pattern.phases.len(),
+                    if pattern.repeat == 0 {
+                        "∞".to_string()
+                    } else {
+                        format!("{}×", pattern.repeat)
+                    }
+                )
+            } else {
+                "Off".to_string()
+            })
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        column![
+            row![
+                checkbox("Use pattern", pattern.enabled)
+                    .on_toggle(Message::BurstPatternToggled)
+                    .width(Length::FillPortion(2)),
+                text("Repeat:").width(Length::FillPortion(1)),
+                text_input("Repeat", &pattern.repeat.to_string())
+                    .on_input(|s| Message::PatternRepeatChanged(s.parse::<u64>().unwrap_or(0)))
+                    .width(Length::FillPortion(1)),
+                row![].width(Length::FillPortion(4)),
+                button(text("Add phase").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press(Message::AddPhase)
+                    .width(Length::FillPortion(1)),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+            phases,
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .align_items(Alignment::Center)
+    .into()
+}
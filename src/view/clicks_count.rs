@@ -3,14 +3,14 @@ use crate::AutoClicker;
 use iced::widget::{row, slider, text};
 use iced::{Alignment, Length};
 
-pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
     row![
         row![
             text("Click counts between intervals:").width(Length::FillPortion(1)),
             text(format!(
                 "{:?} {}",
-                auto_clicker.clicks_count_slider_value,
-                if auto_clicker.clicks_count_slider_value == 1 {
+                auto_clicker.profile.clicks_count_slider_value,
+                if auto_clicker.profile.clicks_count_slider_value == 1 {
                     "click"
                 } else {
                     "clicks"
@@ -23,7 +23,7 @@ pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
         .width(Length::FillPortion(1)),
         slider(
             1..=100,
-            auto_clicker.clicks_count_slider_value,
+            auto_clicker.profile.clicks_count_slider_value,
             Message::ClickCountSliderChanged,
         )
         .width(Length::FillPortion(2))
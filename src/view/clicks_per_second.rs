@@ -0,0 +1,37 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use click_r_engine::cps;
+use iced::widget::{checkbox, row, text, text_input};
+use iced::{Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    row![
+        row![
+            text("Clicks per second:").width(Length::FillPortion(1)),
+            text(match cps::parse_target(&auto_clicker.profile.target_cps) {
+                Some(target_cps) => {
+                    let (interval, clicks_count) = cps::derive(target_cps);
+                    format!("{} × every {}ms", clicks_count, interval.as_millis())
+                }
+                None => "Invalid".to_string(),
+            })
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        row![
+            checkbox("Use CPS instead of interval", auto_clicker.profile.cps_mode)
+                .on_toggle(Message::CpsModeToggled)
+                .width(Length::FillPortion(2)),
+            text("Target CPS:").width(Length::FillPortion(1)),
+            text_input("e.g. 12.5", &auto_clicker.profile.target_cps)
+                .on_input(Message::TargetCpsChanged)
+                .width(Length::FillPortion(2)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .into()
+}
@@ -0,0 +1,63 @@
+use crate::message::Message;
+use crate::panic_key::PanicKey;
+use crate::AutoClicker;
+use click_r_engine::fail_safe::ScreenCorner;
+use iced::widget::{pick_list, row, text, text_input};
+use iced::{Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    row![
+        row![
+            text("Fail-safe:").width(Length::FillPortion(1)),
+            text(
+                if auto_clicker.profile.fail_safe_corner == ScreenCorner::None
+                    && auto_clicker.profile.fail_safe_mouse_move_threshold == 0
+                    && auto_clicker.profile.panic_key == PanicKey::None
+                {
+                    "Off".to_string()
+                } else {
+                    format!(
+                        "{}, {}px, {}",
+                        auto_clicker.profile.fail_safe_corner,
+                        auto_clicker.profile.fail_safe_mouse_move_threshold,
+                        auto_clicker.profile.panic_key
+                    )
+                },
+            )
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        row![
+            text("Corner:").width(Length::FillPortion(1)),
+            pick_list(
+                ScreenCorner::ALL,
+                Some(auto_clicker.profile.fail_safe_corner),
+                Message::FailSafeCornerChanged,
+            )
+            .width(Length::FillPortion(2)),
+            text("Move px:").width(Length::FillPortion(1)),
+            text_input(
+                "Pixels",
+                &auto_clicker
+                    .profile
+                    .fail_safe_mouse_move_threshold
+                    .to_string()
+            )
+            .on_input(|s| Message::FailSafeMouseMoveChanged(s.parse::<u32>().unwrap_or(0)))
+            .width(Length::FillPortion(2)),
+            text("Panic key:").width(Length::FillPortion(1)),
+            pick_list(
+                PanicKey::ALL,
+                Some(auto_clicker.profile.panic_key),
+                Message::PanicKeyChanged,
+            )
+            .width(Length::FillPortion(2)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .into()
+}
@@ -1,20 +1,75 @@
 use crate::message::Message;
+use crate::settings;
 use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::cps;
+use click_r_engine::schedule::{until, DATE_TIME_FORMAT};
 use iced::theme::Button;
-use iced::widget::{button, column, horizontal_rule, row, text, Column};
+use iced::widget::{button, checkbox, column, horizontal_rule, row, text, Column};
 use iced::Alignment;
 use iced::{alignment, Length};
+use std::time::Instant;
 
-pub fn view(auto_clicker: &AutoClicker) -> Column<Message> {
+pub fn view(auto_clicker: &AutoClicker) -> Column<'_, Message> {
     column![
         horizontal_rule(20),
         row![
-            text(format!("Delay Timer: {}s", auto_clicker.delay_timer)),
+            text(match auto_clicker.run_starts_at {
+                Some(run_starts_at)
+                    if *auto_clicker.is_running.lock().unwrap() && Local::now() < run_starts_at =>
+                {
+                    format!(
+                        "Starts at {} (in {}s)",
+                        run_starts_at.format(DATE_TIME_FORMAT),
+                        until(run_starts_at, Local::now()).as_secs()
+                    )
+                }
+                _ => format!("Delay Timer: {}s", auto_clicker.delay_timer),
+            }),
             text(format!("Time Running: {}s", auto_clicker.time_running)),
             text(format!(
                 "Total Clicks: {}",
                 *auto_clicker.total_clicks.lock().unwrap()
-            ))
+            )),
+            text({
+                let measured_cps = match *auto_clicker.clicking_started_at.lock().unwrap() {
+                    Some(clicking_started_at) if *auto_clicker.is_running.lock().unwrap() => {
+                        let elapsed = clicking_started_at.elapsed().as_secs_f64();
+
+                        if elapsed > 0.0 {
+                            *auto_clicker.total_clicks.lock().unwrap() as f64 / elapsed
+                        } else {
+                            0.0
+                        }
+                    }
+                    _ => 0.0,
+                };
+
+                match cps::parse_target(&auto_clicker.profile.target_cps) {
+                    Some(target_cps) if auto_clicker.profile.cps_mode => {
+                        format!("CPS: {:.2} / {}", measured_cps, target_cps)
+                    }
+                    _ => format!("CPS: {:.2}", measured_cps),
+                }
+            }),
+            text(match *auto_clicker.phase_status.lock().unwrap() {
+                Some(phase_status) if *auto_clicker.is_running.lock().unwrap() => format!(
+                    "Phase {} ({}), round {}: {}s left",
+                    phase_status.index + 1,
+                    phase_status.kind,
+                    phase_status.round,
+                    phase_status
+                        .ends_at
+                        .saturating_duration_since(Instant::now())
+                        .as_secs()
+                ),
+                _ => String::new(),
+            }),
+            text(match *auto_clicker.stop_reason.lock().unwrap() {
+                None if *auto_clicker.is_paused.lock().unwrap() => String::from("Paused"),
+                Some(reason) => format!("Ended: {}", reason),
+                None => String::new(),
+            })
         ]
         .align_items(Alignment::Center)
         .spacing(10)
@@ -29,6 +84,25 @@ pub fn view(auto_clicker: &AutoClicker) -> Column<Message> {
                         Some(Message::Start)
                     })
                     .width(Length::FillPortion(1)),
+                button(
+                    text(if *auto_clicker.is_paused.lock().unwrap() {
+                        "Resume"
+                    } else {
+                        "Pause"
+                    })
+                    .horizontal_alignment(alignment::Horizontal::Center)
+                )
+                .on_press_maybe(
+                    match (
+                        *auto_clicker.is_running.lock().unwrap(),
+                        *auto_clicker.is_paused.lock().unwrap(),
+                    ) {
+                        (true, true) => Some(Message::Resume),
+                        (true, false) => Some(Message::Pause),
+                        (false, _) => None,
+                    }
+                )
+                .width(Length::FillPortion(1)),
                 button(text("Stop").horizontal_alignment(alignment::Horizontal::Center))
                     .on_press_maybe(if *auto_clicker.is_running.lock().unwrap() {
                         Some(Message::Stop)
@@ -40,6 +114,12 @@ pub fn view(auto_clicker: &AutoClicker) -> Column<Message> {
             .spacing(10),
             row![].width(Length::Fill),
             row![
+                text(if settings::has_unsaved_changes(auto_clicker) {
+                    "Unsaved changes"
+                } else {
+                    "Saved"
+                }),
+                checkbox("Auto-save", auto_clicker.auto_save).on_toggle(Message::AutoSaveToggled),
                 button(text("Save Settings").horizontal_alignment(alignment::Horizontal::Center))
                     .on_press(Message::SaveSettings)
                     .style(Button::Primary)
@@ -54,6 +134,7 @@ pub fn view(auto_clicker: &AutoClicker) -> Column<Message> {
                 .on_press(Message::ResetToDefaults)
                 .width(Length::FillPortion(1)),
             ]
+            .align_items(Alignment::Center)
             .spacing(10),
         ]
         .align_items(Alignment::Center)
@@ -62,5 +143,4 @@ pub fn view(auto_clicker: &AutoClicker) -> Column<Message> {
     ]
     .spacing(10)
     .align_items(Alignment::Center)
-    .into()
 }
@@ -4,14 +4,25 @@ mod interval;
 mod clicks_count;
 mod delay_before_start;
 mod duration;
+mod fail_safe;
 mod mouse_button;
 mod footer;
 mod page;
+mod schedule;
+mod stop_after;
+mod recurring_schedules;
+mod burst_pattern;
+mod clicks_per_second;
+mod profile;
+mod status;
+mod palette_editor;
+mod theme_import;
+mod profile_sharing;
 
 use crate::AutoClicker;
 use iced::Element;
 use crate::message::Message;
 
-pub fn view_handler(auto_clicker: &AutoClicker) -> Element<Message> {
+pub fn view_handler(auto_clicker: &AutoClicker) -> Element<'_, Message> {
     page::view(auto_clicker)
 }
@@ -6,13 +6,13 @@ use iced::theme::Button;
 use iced::widget::{button, row, text};
 use iced::{Alignment, Length};
 
-pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
     row![
         row![
             text("Choose mouse button:").width(Length::FillPortion(1)),
             text(format!(
                 "{:?}",
-                auto_clicker.selected_mouse_button.lock().unwrap()
+                auto_clicker.profile.selected_mouse_button.lock().unwrap()
             ))
             .width(Length::FillPortion(1)),
         ]
@@ -24,7 +24,8 @@ pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
                 button(text("Left").horizontal_alignment(alignment::Horizontal::Center))
                     .on_press(Message::SelectMouseButton(MouseButton::Left))
                     .style(
-                        if *auto_clicker.selected_mouse_button.lock().unwrap() == MouseButton::Left
+                        if *auto_clicker.profile.selected_mouse_button.lock().unwrap()
+                            == MouseButton::Left
                         {
                             Button::Primary
                         } else {
@@ -35,7 +36,7 @@ pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
                 button(text("Middle").horizontal_alignment(alignment::Horizontal::Center))
                     .on_press(Message::SelectMouseButton(MouseButton::Middle))
                     .style(
-                        if *auto_clicker.selected_mouse_button.lock().unwrap()
+                        if *auto_clicker.profile.selected_mouse_button.lock().unwrap()
                             == MouseButton::Middle
                         {
                             Button::Primary
@@ -47,7 +48,8 @@ pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
                 button(text("Right").horizontal_alignment(alignment::Horizontal::Center))
                     .on_press(Message::SelectMouseButton(MouseButton::Right))
                     .style(
-                        if *auto_clicker.selected_mouse_button.lock().unwrap() == MouseButton::Right
+                        if *auto_clicker.profile.selected_mouse_button.lock().unwrap()
+                            == MouseButton::Right
                         {
                             Button::Primary
                         } else {
@@ -1,25 +1,40 @@
 use crate::message::Message;
 use crate::view::{
-    clicks_count, delay_before_start, duration, footer, interval, mouse_button, parameter_name,
-    theme,
+    burst_pattern, clicks_count, clicks_per_second, delay_before_start, duration, fail_safe,
+    footer, interval, mouse_button, palette_editor, parameter_name, profile, profile_sharing,
+    recurring_schedules, schedule, status, stop_after, theme, theme_import,
 };
 use crate::AutoClicker;
-use iced::widget::column;
+use iced::widget::{column, scrollable};
 use iced::{Alignment, Length};
 
-pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
     column![
-        column![
-            parameter_name::view(),
-            theme::view(auto_clicker),
-            interval::view(auto_clicker),
-            clicks_count::view(auto_clicker),
-            delay_before_start::view(auto_clicker),
-            duration::view(auto_clicker),
-            mouse_button::view(auto_clicker),
-        ]
-        .spacing(20)
-        .align_items(Alignment::Center)
+        profile::view(auto_clicker),
+        profile_sharing::view(auto_clicker),
+        status::view(auto_clicker),
+        scrollable(
+            column![
+                parameter_name::view(),
+                theme::view(auto_clicker),
+                palette_editor::view(auto_clicker),
+                theme_import::view(auto_clicker),
+                interval::view(auto_clicker),
+                clicks_count::view(auto_clicker),
+                clicks_per_second::view(auto_clicker),
+                burst_pattern::view(auto_clicker),
+                delay_before_start::view(auto_clicker),
+                schedule::view(auto_clicker),
+                recurring_schedules::view(auto_clicker),
+                duration::view(auto_clicker),
+                stop_after::view(auto_clicker),
+                mouse_button::view(auto_clicker),
+                fail_safe::view(auto_clicker),
+            ]
+            .spacing(20)
+            .padding([0, 15, 0, 0])
+            .align_items(Alignment::Center)
+        )
         .height(Length::FillPortion(3)),
         footer::view(auto_clicker).height(Length::FillPortion(1))
     ]
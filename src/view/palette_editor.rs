@@ -0,0 +1,64 @@
+use crate::message::Message;
+use crate::theme::{parse_hex, PaletteColor};
+use crate::AutoClicker;
+use iced::theme::Button;
+use iced::widget::{button, column, container, row, text, text_input, Column};
+use iced::{alignment, Alignment, Background, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let Some(draft) = &auto_clicker.palette_draft else {
+        return column![].into();
+    };
+
+    let colors = PaletteColor::ALL
+        .into_iter()
+        .fold(Column::new().spacing(10), |colors, color| {
+            let swatch = parse_hex(draft.color(color));
+
+            colors.push(
+                row![
+                    text(format!("{}:", color)).width(Length::FillPortion(1)),
+                    text_input("#rrggbb", draft.color(color))
+                        .on_input(move |s| Message::PaletteColorChanged(color, s))
+                        .width(Length::FillPortion(2)),
+                    container(text(if swatch.is_some() { "" } else { "Invalid" }))
+                        .style(move |_: &iced::Theme| container::Appearance {
+                            background: swatch.map(Background::Color),
+                            ..Default::default()
+                        })
+                        .width(Length::FillPortion(1))
+                        .height(Length::Fixed(24.0)),
+                ]
+                .align_items(Alignment::Center)
+                .spacing(10),
+            )
+        });
+
+    let custom = draft.to_custom(&auto_clicker.custom_themes);
+
+    row![
+        text("Palette:").width(Length::FillPortion(1)),
+        column![
+            colors,
+            row![
+                text_input("Theme name", &draft.name)
+                    .on_input(Message::CustomThemeNameChanged)
+                    .width(Length::FillPortion(2)),
+                button(
+                    text("Save as new theme").horizontal_alignment(alignment::Horizontal::Center)
+                )
+                .on_press_maybe(custom.is_ok().then_some(Message::SaveCustomTheme))
+                .style(Button::Positive)
+                .width(Length::FillPortion(1)),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+            text(custom.err().unwrap_or_default())
+                .style(auto_clicker.profile.theme.palette().danger),
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .align_items(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,45 @@
+use crate::message::Message;
+use crate::settings::DEFAULT_PROFILE;
+use crate::AutoClicker;
+use iced::theme::Button;
+use iced::widget::{button, pick_list, row, text, text_input};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let is_running = *auto_clicker.is_running.lock().unwrap();
+    let name = auto_clicker.profile_name_input.trim();
+    let name_is_free = !name.is_empty() && !auto_clicker.profiles.contains_key(name);
+    let is_default = auto_clicker.active_profile == DEFAULT_PROFILE;
+    let profiles: Vec<String> = auto_clicker.profiles.keys().cloned().collect();
+
+    row![
+        text("Profile:"),
+        pick_list(
+            profiles,
+            Some(auto_clicker.active_profile.clone()),
+            Message::ProfileSelected,
+        )
+        .width(Length::FillPortion(2)),
+        text_input("Profile name", &auto_clicker.profile_name_input)
+            .on_input(Message::ProfileNameInputChanged)
+            .width(Length::FillPortion(2)),
+        button(text("New").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe((name_is_free && !is_running).then_some(Message::CreateProfile))
+            .width(Length::FillPortion(1)),
+        button(text("Duplicate").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe((name_is_free && !is_running).then_some(Message::DuplicateProfile))
+            .width(Length::FillPortion(1)),
+        button(text("Rename").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe(
+                (name_is_free && !is_default && !is_running).then_some(Message::RenameProfile)
+            )
+            .width(Length::FillPortion(1)),
+        button(text("Delete").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe((!is_default && !is_running).then_some(Message::DeleteProfile))
+            .style(Button::Destructive)
+            .width(Length::FillPortion(1)),
+    ]
+    .align_items(Alignment::Center)
+    .spacing(10)
+    .into()
+}
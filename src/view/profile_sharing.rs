@@ -0,0 +1,66 @@
+use crate::message::Message;
+use crate::settings::CollisionResolution;
+use crate::AutoClicker;
+use iced::theme::Button;
+use iced::widget::{button, column, row, text, text_input};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let is_running = *auto_clicker.is_running.lock().unwrap();
+    let has_path = !auto_clicker.bundle_path.trim().is_empty();
+
+    let mut sharing = column![row![
+        text("Share:"),
+        text_input("Profile bundle file", &auto_clicker.bundle_path)
+            .on_input(Message::BundlePathChanged)
+            .width(Length::FillPortion(4)),
+        button(text("Export").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe(has_path.then_some(Message::ExportProfile))
+            .width(Length::FillPortion(1)),
+        button(text("Export all").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe(has_path.then_some(Message::ExportAllProfiles))
+            .width(Length::FillPortion(1)),
+        button(text("Import").horizontal_alignment(alignment::Horizontal::Center))
+            .on_press_maybe((has_path && !is_running).then_some(Message::ImportProfiles))
+            .width(Length::FillPortion(1)),
+    ]
+    .align_items(Alignment::Center)
+    .spacing(10)]
+    .spacing(10);
+
+    if let Some(bundle) = &auto_clicker.pending_import {
+        sharing = sharing.push(
+            row![
+                text(format!(
+                    "Already existing: {}",
+                    bundle.collisions(&auto_clicker.profiles).join(", ")
+                ))
+                .width(Length::Fill),
+                button(text("Rename").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press_maybe(
+                        (!is_running)
+                            .then_some(Message::ResolveImport(CollisionResolution::Rename))
+                    ),
+                button(text("Overwrite").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press_maybe(
+                        (!is_running)
+                            .then_some(Message::ResolveImport(CollisionResolution::Overwrite))
+                    )
+                    .style(Button::Destructive),
+                button(text("Cancel").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press(Message::CancelImport),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+        );
+    }
+
+    match &auto_clicker.bundle_status {
+        Some(Ok(message)) => sharing.push(text(message)),
+        Some(Err(error)) => {
+            sharing.push(text(error.to_string()).style(auto_clicker.profile.theme.palette().danger))
+        }
+        None => sharing,
+    }
+    .into()
+}
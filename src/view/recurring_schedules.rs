@@ -0,0 +1,117 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::schedule::{parse_cron, DATE_TIME_FORMAT};
+use iced::theme::Button;
+use iced::widget::{button, column, row, text, text_input, Column};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let now = Local::now();
+
+    let next_run = auto_clicker
+        .profile
+        .recurring_schedules
+        .iter()
+        .filter_map(|schedule| schedule.next_run_after(now))
+        .min();
+
+    let schedules = auto_clicker
+        .profile
+        .recurring_schedules
+        .iter()
+        .enumerate()
+        .fold(Column::new().spacing(10), |schedules, (index, schedule)| {
+            schedules.push(
+                row![
+                    text(&schedule.expression).width(Length::FillPortion(3)),
+                    text(if schedule.run_minutes == 0 {
+                        "Duration".to_string()
+                    } else {
+                        format!("{} min", schedule.run_minutes)
+                    })
+                    .width(Length::FillPortion(1)),
+                    text({
+                        let next_run = match schedule.next_run_after(now) {
+                            Some(next_run) => next_run.format(DATE_TIME_FORMAT).to_string(),
+                            None => "Never".to_string(),
+                        };
+
+                        match auto_clicker
+                            .skipped_runs
+                            .iter()
+                            .find(|(skipped, _)| skipped == schedule)
+                        {
+                            Some((_, skipped_at)) => format!(
+                                "{}\nSkipped {}",
+                                next_run,
+                                skipped_at.format(DATE_TIME_FORMAT)
+                            ),
+                            None => next_run,
+                        }
+                    })
+                    .width(Length::FillPortion(3)),
+                    button(text("Remove").horizontal_alignment(alignment::Horizontal::Center))
+                        .on_press(Message::RemoveRecurringSchedule(index))
+                        .style(Button::Destructive)
+                        .width(Length::FillPortion(1)),
+                ]
+                .align_items(Alignment::Center)
+                .spacing(10),
+            )
+        });
+
+    row![
+        row![
+            text("Recurring runs:").width(Length::FillPortion(1)),
+            text(match next_run {
+                Some(next_run) => format!("Next: {}", next_run.format(DATE_TIME_FORMAT)),
+                None => "-".to_string(),
+            })
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        column![
+            schedules,
+            row![
+                text_input(
+                    "Cron, e.g. 55 8 * * MON-FRI",
+                    &auto_clicker.new_schedule_expression
+                )
+                .on_input(Message::NewScheduleExpressionChanged)
+                .width(Length::FillPortion(3)),
+                text_input(
+                    "Minutes",
+                    &auto_clicker.new_schedule_run_minutes.to_string()
+                )
+                .on_input(|s| Message::NewScheduleRunMinutesChanged(s.parse::<u64>().unwrap_or(0)))
+                .width(Length::FillPortion(1)),
+                text(
+                    if auto_clicker.new_schedule_expression.trim().is_empty()
+                        || parse_cron(&auto_clicker.new_schedule_expression).is_ok()
+                    {
+                        ""
+                    } else {
+                        "Invalid"
+                    }
+                )
+                .width(Length::FillPortion(3)),
+                button(text("Add").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press_maybe(
+                        parse_cron(&auto_clicker.new_schedule_expression)
+                            .is_ok()
+                            .then_some(Message::AddRecurringSchedule)
+                    )
+                    .width(Length::FillPortion(1)),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .align_items(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,63 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use chrono::Local;
+use click_r_engine::schedule::{parse_local_date_time, DATE_TIME_FORMAT};
+use iced::widget::{column, row, text, text_input};
+use iced::{Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let now = Local::now();
+
+    let scheduled_start = parse_local_date_time(&auto_clicker.profile.scheduled_start, now);
+    // A stop time without a date refers to its next occurrence after the start.
+    let describe = |input: &str, after| {
+        if input.trim().is_empty() {
+            "-".to_string()
+        } else {
+            match parse_local_date_time(input, after) {
+                Some(date_time) => date_time.format(DATE_TIME_FORMAT).to_string(),
+                None => "Invalid".to_string(),
+            }
+        }
+    };
+
+    row![
+        row![
+            text("Start / stop at:").width(Length::FillPortion(1)),
+            text(format!(
+                "{}\n{}",
+                describe(&auto_clicker.profile.scheduled_start, now),
+                describe(
+                    &auto_clicker.profile.scheduled_stop,
+                    scheduled_start.unwrap_or(now)
+                )
+            ))
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        column![
+            row![
+                text("Start:").width(Length::FillPortion(1)),
+                text_input("YYYY-MM-DD HH:MM:SS", &auto_clicker.profile.scheduled_start)
+                    .on_input(Message::ScheduledStartChanged)
+                    .width(Length::FillPortion(3)),
+                text("Stop:").width(Length::FillPortion(1)),
+                text_input("YYYY-MM-DD HH:MM:SS", &auto_clicker.profile.scheduled_stop)
+                    .on_input(Message::ScheduledStopChanged)
+                    .width(Length::FillPortion(3)),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+            text(match &auto_clicker.schedule_error {
+                Some(error) => error.to_string(),
+                None => String::new(),
+            })
+            .style(auto_clicker.profile.theme.palette().danger),
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .into()
+}
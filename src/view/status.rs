@@ -0,0 +1,59 @@
+use crate::message::Message;
+use crate::settings;
+use crate::AutoClicker;
+use iced::theme::Button;
+use iced::widget::{button, column, row, text};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let mut status = column![].spacing(10);
+
+    if auto_clicker.dry_run {
+        status = status.push(text("Dry run: clicks are counted but not sent."));
+    }
+
+    if let Some(error) = &auto_clicker.settings_error {
+        status = status.push(
+            row![
+                text(error.to_string())
+                    .style(auto_clicker.profile.theme.palette().danger)
+                    .width(Length::Fill),
+                button(text("Dismiss").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press(Message::DismissSettingsError),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+        );
+    }
+
+    if auto_clicker.settings_conflict.is_some() {
+        status = status.push(
+            row![
+                text(format!(
+                    "{} was changed by another program while you have unsaved changes.",
+                    settings::path().display()
+                ))
+                .width(Length::Fill),
+                button(text("Load file").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press_maybe(
+                        (!*auto_clicker.is_running.lock().unwrap())
+                            .then_some(Message::ReloadSettings)
+                    )
+                    .style(Button::Destructive),
+                button(text("Keep mine").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press(Message::KeepLocalSettings),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+        );
+    }
+
+    if auto_clicker.deferred_reload.is_some() {
+        status = status.push(text(format!(
+            "{} was changed by another program, the changes are applied when the run ends.",
+            settings::path().display()
+        )));
+    }
+
+    status.into()
+}
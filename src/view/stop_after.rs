@@ -0,0 +1,42 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use iced::widget::{row, text, text_input};
+use iced::{Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    row![
+        row![
+            text("Stop after:").width(Length::FillPortion(1)),
+            text(
+                match (
+                    auto_clicker.profile.max_total_clicks,
+                    auto_clicker.profile.max_cycles
+                ) {
+                    (0, 0) => "∞".to_string(),
+                    (clicks, 0) => format!("{} clicks", clicks),
+                    (0, cycles) => format!("{} cycles", cycles),
+                    (clicks, cycles) => format!("{} clicks / {} cycles", clicks, cycles),
+                },
+            )
+            .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(1)),
+        row![
+            text("Clicks:").width(Length::FillPortion(1)),
+            text_input("Clicks", &auto_clicker.profile.max_total_clicks.to_string())
+                .on_input(|s| Message::MaxTotalClicksChanged(s.parse::<u64>().unwrap_or(0)))
+                .width(Length::FillPortion(2)),
+            text("Cycles:").width(Length::FillPortion(1)),
+            text_input("Cycles", &auto_clicker.profile.max_cycles.to_string())
+                .on_input(|s| Message::MaxCyclesChanged(s.parse::<u64>().unwrap_or(0)))
+                .width(Length::FillPortion(2)),
+            row![].width(Length::FillPortion(3)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .into()
+}
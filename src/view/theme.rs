@@ -1,19 +1,43 @@
 use crate::message::Message;
 use crate::AutoClicker;
-use iced::widget::{pick_list, row, text};
+use iced::widget::{checkbox, pick_list, row, text};
 use iced::{Alignment, Length, Theme};
 
-pub fn view(auto_clicker: &AutoClicker) -> iced::Element<Message> {
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let themes: Vec<Theme> = Theme::ALL
+        .iter()
+        .cloned()
+        .chain(
+            auto_clicker
+                .custom_themes
+                .iter()
+                .cloned()
+                .map(Theme::Custom),
+        )
+        .collect();
+
     row![
         row![
             text("Theme:").width(Length::FillPortion(1)),
-            text(format!("{:?}", auto_clicker.theme)).width(Length::FillPortion(1)),
+            text(auto_clicker.profile.theme.to_string()).width(Length::FillPortion(1)),
         ]
         .align_items(Alignment::Center)
         .spacing(10)
         .width(Length::FillPortion(1)),
-        pick_list(Theme::ALL, Some(&auto_clicker.theme), Message::ThemeChanged)
-            .width(Length::FillPortion(2))
+        row![
+            pick_list(
+                themes,
+                Some(&auto_clicker.profile.theme),
+                Message::ThemeChanged
+            )
+            .width(Length::FillPortion(3)),
+            checkbox("Edit palette", auto_clicker.palette_draft.is_some())
+                .on_toggle(Message::PaletteEditorToggled)
+                .width(Length::FillPortion(1)),
+        ]
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .width(Length::FillPortion(2)),
     ]
     .align_items(Alignment::Center)
     .spacing(10)
@@ -0,0 +1,37 @@
+use crate::message::Message;
+use crate::AutoClicker;
+use iced::widget::{button, column, row, text, text_input};
+use iced::{alignment, Alignment, Length};
+
+pub fn view(auto_clicker: &AutoClicker) -> iced::Element<'_, Message> {
+    let path = auto_clicker.theme_import_path.trim();
+
+    row![
+        text("Import theme:").width(Length::FillPortion(1)),
+        column![
+            row![
+                text_input(
+                    "Alacritty .toml, base16 .yaml, .itermcolors or Kitty .conf file",
+                    &auto_clicker.theme_import_path
+                )
+                .on_input(Message::ThemeImportPathChanged)
+                .on_submit(Message::ImportTheme)
+                .width(Length::FillPortion(3)),
+                button(text("Import").horizontal_alignment(alignment::Horizontal::Center))
+                    .on_press_maybe((!path.is_empty()).then_some(Message::ImportTheme))
+                    .width(Length::FillPortion(1)),
+            ]
+            .align_items(Alignment::Center)
+            .spacing(10),
+            text(match &auto_clicker.theme_import_error {
+                Some(error) => error.to_string(),
+                None => String::new(),
+            })
+            .style(auto_clicker.profile.theme.palette().danger),
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(2)),
+    ]
+    .align_items(Alignment::Center)
+    .into()
+}
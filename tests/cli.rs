@@ -0,0 +1,254 @@
+//! Runs the `profile` and `settings` commands of the binary against settings files of their own.
+
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+
+/// A directory of its own for each test, removed again afterwards.
+struct Sandbox(PathBuf);
+
+impl Sandbox {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("click-r-cli-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    fn file(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+
+    fn write(&self, name: &str, contents: &Value) -> PathBuf {
+        let path = self.file(name);
+        fs::write(&path, contents.to_string()).unwrap();
+        path
+    }
+
+    /// Runs click-r with `--config` pointing at `config` in this directory.
+    fn click_r(&self, config: &str, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_click-r"))
+            .arg("--config")
+            .arg(self.file(config))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn settings() -> Value {
+    json!({
+        "version": 3,
+        "active_profile": "Farming",
+        "profiles": {
+            "Default": {},
+            "Farming": { "max_cycles": 3 },
+        },
+    })
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn read(path: &Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn lists_profiles_and_marks_the_active_one() {
+    let sandbox = Sandbox::new("list");
+    sandbox.write("settings.json", &settings());
+
+    let output = sandbox.click_r("settings.json", &["profile", "list"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "  Default\n* Farming\n");
+}
+
+#[test]
+fn lists_the_default_profile_without_a_settings_file() {
+    let sandbox = Sandbox::new("list-missing");
+
+    let output = sandbox.click_r("settings.json", &["profile", "list"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "* Default\n");
+    assert!(!sandbox.file("settings.json").exists());
+}
+
+#[test]
+fn shows_a_profile_as_json() {
+    let sandbox = Sandbox::new("show");
+    sandbox.write("settings.json", &settings());
+
+    let output = sandbox.click_r("settings.json", &["profile", "show"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let profile: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(profile, json!({ "max_cycles": 3 }));
+}
+
+#[test]
+fn fails_for_an_unknown_profile() {
+    let sandbox = Sandbox::new("unknown");
+    sandbox.write("settings.json", &settings());
+
+    for args in [
+        &["profile", "show", "Nope"][..],
+        &["profile", "export", "Farming", "Nope"],
+        &["--profile", "Nope"],
+    ] {
+        let output = sandbox.click_r("settings.json", args);
+
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        assert!(
+            stderr(&output).contains("There is no profile called \"Nope\""),
+            "{:?}: {}",
+            args,
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn exports_and_imports_profiles() {
+    let sandbox = Sandbox::new("export-import");
+    sandbox.write("source.json", &settings());
+    let bundle = sandbox.file("bundle.json");
+
+    let exported = sandbox.click_r(
+        "source.json",
+        &[
+            "profile",
+            "export",
+            "Farming",
+            "-o",
+            bundle.to_str().unwrap(),
+        ],
+    );
+    assert!(exported.status.success(), "{}", stderr(&exported));
+
+    let imported = sandbox.click_r(
+        "target.json",
+        &["profile", "import", bundle.to_str().unwrap()],
+    );
+    assert!(imported.status.success(), "{}", stderr(&imported));
+    assert_eq!(stdout(&imported), "Farming\n");
+    assert_eq!(
+        read(&sandbox.file("target.json"))["profiles"]["Farming"],
+        json!({ "max_cycles": 3 })
+    );
+}
+
+#[test]
+fn refuses_to_import_over_existing_profiles_unless_told_how() {
+    let sandbox = Sandbox::new("conflict");
+    let config = sandbox.write("settings.json", &settings());
+    let bundle = sandbox.file("bundle.json");
+    let bundle = bundle.to_str().unwrap();
+
+    let exported = sandbox.click_r("settings.json", &["profile", "export", "-o", bundle]);
+    assert!(exported.status.success(), "{}", stderr(&exported));
+
+    let refused = sandbox.click_r("settings.json", &["profile", "import", bundle]);
+    assert_eq!(refused.status.code(), Some(1));
+    assert!(stderr(&refused).contains("These profiles already exist: Farming"));
+    assert_eq!(read(&config), settings());
+
+    let renamed = sandbox.click_r(
+        "settings.json",
+        &["profile", "import", bundle, "--on-conflict", "rename"],
+    );
+    assert!(renamed.status.success(), "{}", stderr(&renamed));
+    assert_eq!(stdout(&renamed), "Farming (2)\n");
+}
+
+#[test]
+fn refuses_to_import_a_file_that_is_no_bundle() {
+    let sandbox = Sandbox::new("not-a-bundle");
+    let config = sandbox.write("settings.json", &settings());
+    let other = sandbox.write("other.json", &json!({ "profiles": {} }));
+
+    for file in [other, sandbox.file("missing.json")] {
+        let output = sandbox.click_r(
+            "settings.json",
+            &["profile", "import", file.to_str().unwrap()],
+        );
+
+        assert_eq!(output.status.code(), Some(1), "{}", file.display());
+        assert!(!stderr(&output).is_empty());
+    }
+
+    assert_eq!(read(&config), settings());
+}
+
+#[test]
+fn validates_settings_files() {
+    let sandbox = Sandbox::new("validate");
+    let valid = sandbox.write("valid.json", &settings());
+    let broken = sandbox.write(
+        "broken.json",
+        &json!({ "version": 3, "profiles": { "Farming": { "max_cycles": "many" } } }),
+    );
+
+    let output = sandbox.click_r(
+        "settings.json",
+        &["settings", "validate", valid.to_str().unwrap()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("is valid: 2 profiles, \"Farming\" active"));
+
+    let output = sandbox.click_r(
+        "settings.json",
+        &["settings", "validate", broken.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("profile \"Farming\""));
+    assert_eq!(
+        read(&broken)["profiles"]["Farming"],
+        json!({ "max_cycles": "many" })
+    );
+}
+
+#[test]
+fn prints_the_settings_path() {
+    let sandbox = Sandbox::new("path");
+
+    let output = sandbox.click_r("settings.json", &["settings", "path"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output).trim_end(),
+        sandbox.file("settings.json").display().to_string()
+    );
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    let sandbox = Sandbox::new("arguments");
+
+    for args in [
+        &["profile", "import"][..],
+        &["profile", "export", "--all", "Farming"],
+        &["--start", "profile", "list"],
+        &["settings", "check"],
+    ] {
+        let output = sandbox.click_r("settings.json", args);
+
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(stderr(&output).starts_with("error:"), "{:?}", args);
+    }
+}